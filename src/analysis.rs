@@ -0,0 +1,598 @@
+//! Static analysis of expressions, finding filters that can never match or always do.
+//!
+//! The analysis works on the [disjunctive normal form](Expression::to_dnf) of an expression
+//! and decides each of its clauses key by key. Values are compared as the [`ValueType`] of
+//! their key, text by code point and case-sensitively.
+//!
+//! A key is missing the way a column is `NULL` in SQL: `-` holds only for a key without a
+//! value, which fails every comparison and passes its negation, so `!"age" > "18"` holds for
+//! a missing age. SQL leaves such a negation unknown instead, which only ever makes fewer
+//! rows match, so an expression found [unsatisfiable](Analysis::Unsatisfiable) matches
+//! nothing in SQL either. A [tautology](Analysis::Tautology) in SQL may still skip rows with
+//! `NULL` in a negated comparison.
+//!
+//! The answers are therefore for the SQL interpreters, not for the evaluator: by default its
+//! `-` holds for the text `null` or `none` rather than for a missing pair, so
+//! `"name" - & "name" = "null"` is unsatisfiable here but passes the evaluator for a name of
+//! `null`.
+
+use std::{cmp::Ordering, collections::HashMap};
+
+use regex::RegexBuilder;
+
+use crate::{
+    wildcard::{self, Token},
+    Expression, Node,
+};
+
+/// How the values of a key compare.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ValueType {
+    Integer,
+    Float,
+    #[default]
+    Text,
+}
+
+/// Types of the keys, where a key that is not in it is [`ValueType::Text`].
+pub type ValueTypes = HashMap<String, ValueType>;
+
+/// Comparisons of one key that cannot all hold at once.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Conflict {
+    pub key: String,
+    /// The comparisons, possibly negated, as they appear in the disjunctive normal form.
+    pub expressions: Vec<Expression>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Analysis {
+    /// Some values match and some do not.
+    Satisfiable,
+    /// Every value matches.
+    Tautology,
+    /// No value matches, because of the conflicts in each clause of the disjunctive normal
+    /// form. A clause that is constant false has none.
+    Unsatisfiable(Vec<Conflict>),
+    /// The expression uses something the analysis cannot reason about, such as a regex, or
+    /// its normal form would be too large.
+    Unknown,
+}
+
+/// Whether every value passing one expression passes another.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Implication {
+    Yes,
+    No,
+    Unknown,
+}
+
+/// Comparisons of candidates with targets that deciding one key may take before giving up.
+const MAX_TESTS: usize = 1 << 20;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Outcome {
+    Satisfiable,
+    Unsatisfiable,
+    Unknown,
+}
+
+#[derive(Debug, PartialEq, PartialOrd)]
+enum Value {
+    Integer(i64),
+    Float(f64),
+    Text(String),
+}
+
+fn value(value_type: ValueType, s: &str) -> Option<Value> {
+    match value_type {
+        ValueType::Integer => s.parse().ok().map(Value::Integer),
+        ValueType::Float => s.parse().ok().map(Value::Float),
+        ValueType::Text => Some(Value::Text(s.to_string())),
+    }
+}
+
+/// A comparison of a clause, which may be negated.
+#[derive(Clone, Copy)]
+struct Literal<'a> {
+    expression: &'a Expression,
+    node: &'a Node,
+    negated: bool,
+}
+
+impl<'a> Literal<'a> {
+    fn new(expression: &'a Expression) -> Self {
+        match &expression.node {
+            Node::Not(inner) => Self {
+                expression,
+                node: &inner.node,
+                negated: true,
+            },
+            node => Self {
+                expression,
+                node,
+                negated: false,
+            },
+        }
+    }
+
+    fn key(&self) -> &'a str {
+        match self.node {
+            Node::Equal(key, _)
+            | Node::EqualCI(key, _)
+            | Node::Greater(key, _)
+            | Node::Less(key, _)
+            | Node::GreaterEqual(key, _)
+            | Node::LessEqual(key, _)
+            | Node::NotEqual(key, _)
+            | Node::Between(key, _)
+            | Node::Contains(key, _)
+            | Node::StartsWith(key, _)
+            | Node::EndsWith(key, _)
+            | Node::Wildcard(key, _)
+            | Node::Regex(key, _)
+            | Node::RegexCI(key, _)
+            | Node::Any(key, _)
+            | Node::Null(key) => key,
+            Node::All(_) | Node::AnyOf(_) | Node::Not(_) => "",
+        }
+    }
+
+    fn is_null(&self) -> bool {
+        matches!(self.node, Node::Null(_))
+    }
+}
+
+/// Whether `candidate` matches `target`, or `None` if that depends on the case.
+fn case_sensitive(candidate: &str, target: &str, matches: fn(&str, &str) -> bool) -> Option<bool> {
+    match matches(candidate, target) {
+        false if matches(&candidate.to_lowercase(), &target.to_lowercase()) => None,
+        matches => Some(matches),
+    }
+}
+
+/// Whether `candidate` matches the regex `target`, or `None` if the regex is invalid.
+fn regex_matches(target: &str, case_insensitive: bool, candidate: &str) -> Option<bool> {
+    let regex = RegexBuilder::new(target)
+        .case_insensitive(case_insensitive)
+        .build()
+        .ok()?;
+    Some(regex.is_match(candidate))
+}
+
+/// Whether a non-missing `candidate` passes the comparison, or `None` if that cannot be told.
+fn test(node: &Node, value_type: ValueType, candidate: &str) -> Option<bool> {
+    let compare =
+        |target: &str| value(value_type, candidate)?.partial_cmp(&value(value_type, target)?);
+    Some(match node {
+        Node::Equal(_, target) => compare(target)? == Ordering::Equal,
+        Node::EqualCI(_, target) => match value_type {
+            ValueType::Text => candidate.to_lowercase() == target.to_lowercase(),
+            _ => compare(target)? == Ordering::Equal,
+        },
+        Node::Greater(_, target) => compare(target)? == Ordering::Greater,
+        Node::Less(_, target) => compare(target)? == Ordering::Less,
+        Node::GreaterEqual(_, target) => compare(target)? != Ordering::Less,
+        Node::LessEqual(_, target) => compare(target)? != Ordering::Greater,
+        Node::NotEqual(_, target) => compare(target)? != Ordering::Equal,
+        Node::Between(_, bounds) => {
+            let lower = compare(&bounds.lower)?;
+            let upper = compare(&bounds.upper)?;
+            (lower == Ordering::Greater || (bounds.lower_inclusive && lower == Ordering::Equal))
+                && (upper == Ordering::Less || (bounds.upper_inclusive && upper == Ordering::Equal))
+        }
+        // MySQL matches patterns as the collation of the column says, which may ignore case.
+        Node::Contains(_, target) => case_sensitive(candidate, target, |candidate, target| {
+            candidate.contains(target)
+        })?,
+        Node::StartsWith(_, target) => case_sensitive(candidate, target, |candidate, target| {
+            candidate.starts_with(target)
+        })?,
+        Node::EndsWith(_, target) => case_sensitive(candidate, target, |candidate, target| {
+            candidate.ends_with(target)
+        })?,
+        Node::Wildcard(_, target) => case_sensitive(candidate, target, |candidate, target| {
+            wildcard::matches(target, candidate)
+        })?,
+        Node::Regex(_, target) => match (
+            regex_matches(target, false, candidate)?,
+            regex_matches(target, true, candidate)?,
+        ) {
+            (false, true) => return None,
+            (matches, _) => matches,
+        },
+        Node::RegexCI(_, target) => regex_matches(target, true, candidate)?,
+        Node::Any(_, targets) => {
+            let mut found = false;
+            for target in targets {
+                found |= compare(target)? == Ordering::Equal;
+            }
+            found
+        }
+        Node::Null(_) => false,
+        Node::All(_) | Node::AnyOf(_) | Node::Not(_) => return None,
+    })
+}
+
+/// Values worth trying for a key that is not missing, and whether they are the only values
+/// that can pass, which is the case when the key must equal one of a few targets.
+fn candidates(literals: &[Literal], value_type: ValueType) -> (Vec<String>, bool) {
+    for literal in literals {
+        match (literal.node, literal.negated) {
+            (Node::Equal(_, target), false) | (Node::NotEqual(_, target), true) => {
+                return (vec![target.clone()], true)
+            }
+            (Node::Any(_, targets), false) => return (targets.clone(), true),
+            _ => {}
+        }
+    }
+    let mut targets = Vec::new();
+    for literal in literals {
+        match literal.node {
+            Node::Equal(_, target)
+            | Node::EqualCI(_, target)
+            | Node::Greater(_, target)
+            | Node::Less(_, target)
+            | Node::GreaterEqual(_, target)
+            | Node::LessEqual(_, target)
+            | Node::NotEqual(_, target)
+            | Node::Contains(_, target)
+            | Node::StartsWith(_, target)
+            | Node::EndsWith(_, target) => targets.push(target.clone()),
+            Node::Wildcard(_, target) => targets.push(
+                wildcard::tokens(target)
+                    .into_iter()
+                    .filter_map(|token| match token {
+                        Token::Char(c) => Some(c),
+                        Token::AnyRun => None,
+                        Token::AnyChar => Some('a'),
+                    })
+                    .collect(),
+            ),
+            Node::Between(_, bounds) => {
+                targets.push(bounds.lower.clone());
+                targets.push(bounds.upper.clone());
+            }
+            Node::Any(_, any) => targets.extend(any.iter().cloned()),
+            _ => {}
+        }
+    }
+    // Every target and its neighbours, so ranges and exclusions leave one of them.
+    let mut candidates = Vec::new();
+    match value_type {
+        ValueType::Integer => {
+            let numbers = targets.iter().filter_map(|t| t.parse::<i64>().ok());
+            candidates.push("0".to_string());
+            for n in numbers {
+                candidates.extend(
+                    [n.checked_sub(1), Some(n), n.checked_add(1)]
+                        .into_iter()
+                        .flatten()
+                        .map(|n| n.to_string()),
+                );
+            }
+        }
+        ValueType::Float => {
+            let mut numbers = targets
+                .iter()
+                .filter_map(|t| t.parse::<f64>().ok())
+                .collect::<Vec<_>>();
+            numbers.sort_by(f64::total_cmp);
+            numbers.dedup();
+            candidates.push("0".to_string());
+            for (i, a) in numbers.iter().enumerate() {
+                candidates.extend([a - 1.0, *a, a + 1.0].map(|n| n.to_string()));
+                // Only a value between neighbours can fall into a gap no target is in.
+                if let Some(b) = numbers.get(i + 1) {
+                    candidates.push(((a + b) / 2.0).to_string());
+                }
+            }
+        }
+        ValueType::Text => {
+            candidates.push(String::new());
+            for target in targets {
+                candidates.push(format!("{}a", target));
+                candidates.push(target);
+            }
+        }
+    }
+    (candidates, false)
+}
+
+/// Whether the bounds and exclusions of the literals leave no value at all.
+fn range_is_empty(literals: &[Literal], value_type: ValueType) -> bool {
+    // The bounds as (value, inclusive), where a higher lower bound or a lower upper bound
+    // is tighter, as is an exclusive one at the same value.
+    let mut lower: Option<(Value, bool)> = None;
+    let mut upper: Option<(Value, bool)> = None;
+    let mut excluded = Vec::new();
+    // Each bound as (target, inclusive, is_lower).
+    let mut bounds = Vec::new();
+    for literal in literals {
+        match (literal.node, literal.negated) {
+            (Node::Greater(_, target), false) => bounds.push((target, false, true)),
+            (Node::Less(_, target), true) => bounds.push((target, true, true)),
+            (Node::Less(_, target), false) => bounds.push((target, false, false)),
+            (Node::Greater(_, target), true) => bounds.push((target, true, false)),
+            (Node::GreaterEqual(_, target), false) => bounds.push((target, true, true)),
+            (Node::LessEqual(_, target), true) => bounds.push((target, false, true)),
+            (Node::LessEqual(_, target), false) => bounds.push((target, true, false)),
+            (Node::GreaterEqual(_, target), true) => bounds.push((target, false, false)),
+            (Node::Between(_, between), false) => {
+                bounds.push((&between.lower, between.lower_inclusive, true));
+                bounds.push((&between.upper, between.upper_inclusive, false));
+            }
+            (Node::Equal(_, target), true) | (Node::NotEqual(_, target), false) => {
+                excluded.extend(value(value_type, target));
+            }
+            (Node::Any(_, targets), true) => {
+                excluded.extend(targets.iter().filter_map(|t| value(value_type, t)));
+            }
+            _ => {}
+        }
+    }
+    for (bound, inclusive, is_lower) in bounds {
+        let Some(bound) = value(value_type, bound) else {
+            return false;
+        };
+        let (current, tighter) = if is_lower {
+            (&mut lower, Ordering::Greater)
+        } else {
+            (&mut upper, Ordering::Less)
+        };
+        let replace = match current {
+            None => true,
+            Some((value, current_inclusive)) => match bound.partial_cmp(value) {
+                Some(Ordering::Equal) => *current_inclusive && !inclusive,
+                ordering => ordering == Some(tighter),
+            },
+        };
+        if replace {
+            *current = Some((bound, inclusive));
+        }
+    }
+    let (Some((lower, lower_inclusive)), Some((upper, upper_inclusive))) = (lower, upper) else {
+        return false;
+    };
+    match (lower, upper) {
+        (Value::Integer(lower), Value::Integer(upper)) => {
+            let first = lower as i128 + i128::from(!lower_inclusive);
+            let last = upper as i128 - i128::from(!upper_inclusive);
+            // With more values in the range than excluded ones, some are left.
+            first > last
+                || (last - first < excluded.len() as i128
+                    && (first..=last).all(|n| excluded.contains(&Value::Integer(n as i64))))
+        }
+        // Between two different values there are always others, but not always between
+        // two strings, so this may miss a few empty ranges of text.
+        (lower, upper) => match lower.partial_cmp(&upper) {
+            Some(Ordering::Greater) => true,
+            Some(Ordering::Equal) => {
+                !(lower_inclusive && upper_inclusive) || excluded.contains(&lower)
+            }
+            _ => false,
+        },
+    }
+}
+
+/// Whether some value of one key passes all of its literals.
+fn decide(literals: &[Literal], value_type: ValueType) -> Outcome {
+    let requires_null = literals.iter().any(|l| l.is_null() && !l.negated);
+    let forbids_null = literals.iter().any(|l| l.is_null() == l.negated);
+    if requires_null || !forbids_null {
+        // A missing value fails every comparison and passes every negated one.
+        return match requires_null && forbids_null {
+            true => Outcome::Unsatisfiable,
+            false => Outcome::Satisfiable,
+        };
+    }
+    if range_is_empty(literals, value_type) {
+        return Outcome::Unsatisfiable;
+    }
+    let comparisons = literals
+        .iter()
+        .filter(|literal| !literal.is_null())
+        .collect::<Vec<_>>();
+    let (candidates, exhaustive) = candidates(literals, value_type);
+    let targets = comparisons
+        .iter()
+        .map(|literal| match literal.node {
+            Node::Any(_, targets) => targets.len().max(1),
+            _ => 1,
+        })
+        .sum::<usize>();
+    if candidates.len().saturating_mul(targets) > MAX_TESTS {
+        return Outcome::Unknown;
+    }
+    let mut undecided = false;
+    for candidate in candidates {
+        let mut passes = Some(true);
+        for literal in &comparisons {
+            match test(literal.node, value_type, &candidate) {
+                Some(result) if result != literal.negated => {}
+                Some(_) => {
+                    passes = Some(false);
+                    break;
+                }
+                None => passes = None,
+            }
+        }
+        match passes {
+            Some(true) => return Outcome::Satisfiable,
+            Some(false) => {}
+            None => undecided = true,
+        }
+    }
+    if exhaustive && !undecided {
+        Outcome::Unsatisfiable
+    } else {
+        Outcome::Unknown
+    }
+}
+
+/// The smallest set of literals, up to two of them, that cannot hold at once.
+fn conflict(key: &str, literals: &[Literal], value_type: ValueType) -> Conflict {
+    let expressions =
+        |literals: &[Literal]| literals.iter().map(|l| l.expression.clone()).collect();
+    for (i, first) in literals.iter().enumerate() {
+        for second in &literals[i..] {
+            let pair = if first.expression == second.expression {
+                vec![*first]
+            } else {
+                vec![*first, *second]
+            };
+            if decide(&pair, value_type) == Outcome::Unsatisfiable {
+                return Conflict {
+                    key: key.to_string(),
+                    expressions: expressions(&pair),
+                };
+            }
+        }
+    }
+    Conflict {
+        key: key.to_string(),
+        expressions: expressions(literals),
+    }
+}
+
+/// Decides a clause of the disjunctive normal form, adding what conflicts in it.
+fn decide_clause(
+    clause: &[Expression],
+    types: &ValueTypes,
+    conflicts: &mut Vec<Conflict>,
+) -> Outcome {
+    let mut keys: Vec<(&str, Vec<Literal>)> = Vec::new();
+    for expression in clause {
+        let literal = Literal::new(expression);
+        match keys.iter_mut().find(|(key, _)| *key == literal.key()) {
+            Some((_, literals)) => literals.push(literal),
+            None => keys.push((literal.key(), vec![literal])),
+        }
+    }
+    let mut outcome = Outcome::Satisfiable;
+    for (key, literals) in keys {
+        let value_type = types.get(key).copied().unwrap_or_default();
+        match decide(&literals, value_type) {
+            Outcome::Satisfiable => {}
+            Outcome::Unsatisfiable => {
+                outcome = Outcome::Unsatisfiable;
+                let conflict = conflict(key, &literals, value_type);
+                if !conflicts.contains(&conflict) {
+                    conflicts.push(conflict);
+                }
+            }
+            Outcome::Unknown => {
+                if outcome == Outcome::Satisfiable {
+                    outcome = Outcome::Unknown;
+                }
+            }
+        }
+    }
+    outcome
+}
+
+/// The clauses of the disjunctive normal form, each a list of literals.
+fn clauses(expression: &Expression) -> Option<Vec<Vec<Expression>>> {
+    match expression.to_dnf().ok()?.node {
+        Node::AnyOf(clauses) => Some(
+            clauses
+                .into_iter()
+                .map(|clause| match clause.node {
+                    Node::All(literals) => literals,
+                    node => vec![node.into()],
+                })
+                .collect(),
+        ),
+        node => Some(vec![vec![node.into()]]),
+    }
+}
+
+/// Whether no value can pass `expression`, and if not, the conflicts in every clause.
+fn unsatisfiable(expression: &Expression, types: &ValueTypes) -> Result<Vec<Conflict>, Outcome> {
+    let clauses = clauses(expression).ok_or(Outcome::Unknown)?;
+    let mut conflicts = Vec::new();
+    let mut outcome = Outcome::Unsatisfiable;
+    for clause in &clauses {
+        match decide_clause(clause, types, &mut conflicts) {
+            Outcome::Satisfiable => return Err(Outcome::Satisfiable),
+            Outcome::Unknown => outcome = Outcome::Unknown,
+            Outcome::Unsatisfiable => {}
+        }
+    }
+    match outcome {
+        Outcome::Unsatisfiable => Ok(conflicts),
+        outcome => Err(outcome),
+    }
+}
+
+/// Analyzes `expression`, comparing the values of each key as given by `types`.
+pub fn analyze(expression: &Expression, types: &ValueTypes) -> Analysis {
+    let outcome = match unsatisfiable(expression, types) {
+        Ok(conflicts) => return Analysis::Unsatisfiable(conflicts),
+        Err(outcome) => outcome,
+    };
+    let negation = Expression::from(Node::Not(Box::new(expression.clone())));
+    // Some values must also fail, or the expression may well be a tautology.
+    match (outcome, unsatisfiable(&negation, types)) {
+        (_, Ok(_)) => Analysis::Tautology,
+        (Outcome::Satisfiable, Err(Outcome::Satisfiable)) => Analysis::Satisfiable,
+        _ => Analysis::Unknown,
+    }
+}
+
+/// `expression`, or its negation when `negated`, rewritten so that it holds exactly where SQL
+/// finds it true.
+///
+/// SQL has a third truth value for a comparison with `NULL`, which its negation keeps, so
+/// a negated comparison only holds for a value that is there.
+fn sql_true(expression: &Expression, negated: bool) -> Expression {
+    let operands = |expressions: &[Expression]| -> Vec<Expression> {
+        expressions
+            .iter()
+            .map(|expression| sql_true(expression, negated))
+            .collect()
+    };
+    match &expression.node {
+        Node::Not(inner) => sql_true(inner, !negated),
+        // De Morgan's laws hold for the three truth values as well.
+        Node::All(expressions) if negated => Node::AnyOf(operands(expressions)).into(),
+        Node::All(expressions) => Node::All(operands(expressions)).into(),
+        Node::AnyOf(expressions) if negated => Node::All(operands(expressions)).into(),
+        Node::AnyOf(expressions) => Node::AnyOf(operands(expressions)).into(),
+        _ if !negated => expression.clone(),
+        Node::Null(_) => Node::Not(Box::new(expression.clone())).into(),
+        _ => {
+            let key = Literal::new(expression).key().to_string();
+            Node::All(vec![
+                Node::Not(Box::new(expression.clone())).into(),
+                Node::Not(Box::new(Node::Null(key).into())).into(),
+            ])
+            .into()
+        }
+    }
+}
+
+/// Whether SQL finds `other` true for every row it finds `expression` true for, so that a
+/// search for `expression` can reuse the rows of `other`.
+///
+/// Unlike the rest of the analysis, this follows how SQL treats a negated comparison with
+/// `NULL`, so `"age" -` does not imply `!"age" > "18"`.
+pub fn implies(expression: &Expression, other: &Expression, types: &ValueTypes) -> Implication {
+    let counterexample = Expression::from(Node::All(vec![
+        sql_true(expression, false),
+        // Not true, i.e. false or unknown, which is the negation in two-valued logic.
+        Node::Not(Box::new(sql_true(other, false))).into(),
+    ]));
+    match unsatisfiable(&counterexample, types) {
+        Ok(_) => Implication::Yes,
+        Err(Outcome::Satisfiable) => Implication::No,
+        Err(_) => Implication::Unknown,
+    }
+}
+
+impl Expression {
+    /// Whether this expression is narrower than `other`, see [`implies`].
+    pub fn implies(&self, other: &Expression, types: &ValueTypes) -> Implication {
+        implies(self, other, types)
+    }
+}
@@ -0,0 +1,151 @@
+//! Typed construction of expressions, as an alternative to formatting and parsing GSS.
+//!
+//! ```
+//! use flp_gsp::builder::field;
+//!
+//! let adults = field("age").gt("18") & !field("age").is_null();
+//! let expression = adults.and(field("sex").any_of(["male", "female"]));
+//! assert_eq!(
+//!     expression.to_string(),
+//!     r#""age" > "18" & !"age" - & "sex" ? ["male", "female"]"#
+//! );
+//! ```
+
+use std::ops::{BitAnd, BitOr, Not};
+
+use crate::{Bounds, Expression, Node};
+
+/// The key every comparison made from it is about.
+#[derive(Clone, Debug)]
+pub struct Field {
+    key: String,
+}
+
+pub fn field(key: impl Into<String>) -> Field {
+    Field { key: key.into() }
+}
+
+impl Field {
+    pub fn eq(self, target: impl Into<String>) -> Expression {
+        Node::Equal(self.key, target.into()).into()
+    }
+
+    pub fn eq_ci(self, target: impl Into<String>) -> Expression {
+        Node::EqualCI(self.key, target.into()).into()
+    }
+
+    pub fn gt(self, target: impl Into<String>) -> Expression {
+        Node::Greater(self.key, target.into()).into()
+    }
+
+    pub fn lt(self, target: impl Into<String>) -> Expression {
+        Node::Less(self.key, target.into()).into()
+    }
+
+    pub fn ge(self, target: impl Into<String>) -> Expression {
+        Node::GreaterEqual(self.key, target.into()).into()
+    }
+
+    pub fn le(self, target: impl Into<String>) -> Expression {
+        Node::LessEqual(self.key, target.into()).into()
+    }
+
+    pub fn ne(self, target: impl Into<String>) -> Expression {
+        Node::NotEqual(self.key, target.into()).into()
+    }
+
+    /// Between `lower` and `upper`, both included.
+    pub fn between(self, lower: impl Into<String>, upper: impl Into<String>) -> Expression {
+        let bounds = Bounds {
+            lower: lower.into(),
+            lower_inclusive: true,
+            upper: upper.into(),
+            upper_inclusive: true,
+        };
+        Node::Between(self.key, bounds).into()
+    }
+
+    pub fn contains(self, target: impl Into<String>) -> Expression {
+        Node::Contains(self.key, target.into()).into()
+    }
+
+    pub fn starts_with(self, target: impl Into<String>) -> Expression {
+        Node::StartsWith(self.key, target.into()).into()
+    }
+
+    pub fn ends_with(self, target: impl Into<String>) -> Expression {
+        Node::EndsWith(self.key, target.into()).into()
+    }
+
+    pub fn wildcard(self, target: impl Into<String>) -> Expression {
+        Node::Wildcard(self.key, target.into()).into()
+    }
+
+    pub fn regex(self, target: impl Into<String>) -> Expression {
+        Node::Regex(self.key, target.into()).into()
+    }
+
+    pub fn regex_ci(self, target: impl Into<String>) -> Expression {
+        Node::RegexCI(self.key, target.into()).into()
+    }
+
+    pub fn any_of<T: Into<String>>(self, targets: impl IntoIterator<Item = T>) -> Expression {
+        Node::Any(self.key, targets.into_iter().map(Into::into).collect()).into()
+    }
+
+    pub fn is_null(self) -> Expression {
+        Node::Null(self.key).into()
+    }
+}
+
+impl Expression {
+    /// Both expressions, merged into one flat `All` if either already is one.
+    pub fn and(self, other: Expression) -> Expression {
+        let mut expressions = match self.node {
+            Node::All(expressions) => expressions,
+            node => vec![node.into()],
+        };
+        match other.node {
+            Node::All(mut others) => expressions.append(&mut others),
+            node => expressions.push(node.into()),
+        }
+        Node::All(expressions).into()
+    }
+
+    /// Either expression, merged into one flat `AnyOf` if either already is one.
+    pub fn or(self, other: Expression) -> Expression {
+        let mut expressions = match self.node {
+            Node::AnyOf(expressions) => expressions,
+            node => vec![node.into()],
+        };
+        match other.node {
+            Node::AnyOf(mut others) => expressions.append(&mut others),
+            node => expressions.push(node.into()),
+        }
+        Node::AnyOf(expressions).into()
+    }
+}
+
+impl BitAnd for Expression {
+    type Output = Expression;
+
+    fn bitand(self, other: Expression) -> Expression {
+        self.and(other)
+    }
+}
+
+impl BitOr for Expression {
+    type Output = Expression;
+
+    fn bitor(self, other: Expression) -> Expression {
+        self.or(other)
+    }
+}
+
+impl Not for Expression {
+    type Output = Expression;
+
+    fn not(self) -> Expression {
+        Node::Not(Box::new(self)).into()
+    }
+}
@@ -0,0 +1,74 @@
+use std::{
+    collections::hash_map::DefaultHasher,
+    hash::{Hash, Hasher},
+};
+
+use crate::{
+    visit::{fold_expressions, Fold},
+    Expression, Node,
+};
+
+/// Sorts and deduplicates the operands of a group, which stands for its only operand if
+/// that is all that is left.
+fn junction(mut expressions: Vec<Expression>, join: fn(Vec<Expression>) -> Node) -> Expression {
+    expressions.sort();
+    expressions.dedup();
+    if expressions.len() == 1 {
+        return expressions.remove(0);
+    }
+    join(expressions).into()
+}
+
+struct Canonicalize;
+
+impl Fold for Canonicalize {
+    fn fold_all(&mut self, expressions: Vec<Expression>) -> Expression {
+        let mut operands = Vec::with_capacity(expressions.len());
+        for expression in fold_expressions(self, expressions) {
+            match expression.node {
+                Node::All(nested) => operands.extend(nested),
+                node => operands.push(node.into()),
+            }
+        }
+        junction(operands, Node::All)
+    }
+
+    fn fold_any_of(&mut self, expressions: Vec<Expression>) -> Expression {
+        let mut operands = Vec::with_capacity(expressions.len());
+        for expression in fold_expressions(self, expressions) {
+            match expression.node {
+                Node::AnyOf(nested) => operands.extend(nested),
+                node => operands.push(node.into()),
+            }
+        }
+        junction(operands, Node::AnyOf)
+    }
+
+    fn fold_any(&mut self, key: String, mut targets: Vec<String>) -> Expression {
+        targets.sort();
+        targets.dedup();
+        Node::Any(key, targets).into()
+    }
+}
+
+impl Expression {
+    /// An equivalent expression in which operands that are interchangeable are written in one
+    /// fixed way: groups of the same kind are flattened into their parent, operands of
+    /// `All`/`AnyOf` and values of `Any` are sorted with duplicates removed, and groups of a
+    /// single operand are replaced by that operand.
+    ///
+    /// Two expressions that only differ in those respects have equal canonical forms, e.g.
+    /// `("a" = "1" & "b" = "2")` and `("b" = "2" & "a" = "1")`.
+    pub fn canonical(&self) -> Expression {
+        Canonicalize.fold_expression(self.clone())
+    }
+
+    /// Hash of the [canonical](Expression::canonical) form, so expressions that only differ
+    /// in the order or repetition of operands hash equal. Suitable as a cache key within one
+    /// build of the program, but not stable across Rust versions.
+    pub fn canonical_hash(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        self.canonical().hash(&mut hasher);
+        hasher.finish()
+    }
+}
@@ -0,0 +1,183 @@
+use std::fmt::{self, Display, Formatter, Write};
+
+use crate::{Bounds, Expression, Node};
+
+/// Indentation of one level in the pretty form.
+const INDENT: &str = "    ";
+
+/// Writes `s` as a GSS string, escaping `\` and `"` the way `parser::atom::text` expects.
+fn write_text(f: &mut Formatter<'_>, s: &str) -> fmt::Result {
+    f.write_char('"')?;
+    for c in s.chars() {
+        if c == '\\' || c == '"' {
+            f.write_char('\\')?;
+        }
+        f.write_char(c)?;
+    }
+    f.write_char('"')
+}
+
+fn write_indent(f: &mut Formatter<'_>, level: usize) -> fmt::Result {
+    for _ in 0..level {
+        f.write_str(INDENT)?;
+    }
+    Ok(())
+}
+
+/// Skips groups of a single expression, which print the same as the expression itself and
+/// so parse back as that expression rather than as a group.
+fn unwrap(mut expression: &Expression) -> &Expression {
+    while let Node::All(expressions) | Node::AnyOf(expressions) = &expression.node {
+        match expressions.as_slice() {
+            [single] => expression = single,
+            _ => break,
+        }
+    }
+    expression
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Context {
+    Top,
+    All,
+    AnyOf,
+    Not,
+}
+
+/// Whether `expression` needs a group to keep its meaning when it appears in `context`.
+fn needs_group(expression: &Expression, context: Context) -> bool {
+    match &expression.node {
+        Node::All(expressions) if !expressions.is_empty() => {
+            matches!(context, Context::All | Context::Not)
+        }
+        Node::AnyOf(expressions) if !expressions.is_empty() => context != Context::Top,
+        _ => false,
+    }
+}
+
+fn write_operand(
+    f: &mut Formatter<'_>,
+    expression: &Expression,
+    context: Context,
+    level: usize,
+) -> fmt::Result {
+    let expression = unwrap(expression);
+    if !needs_group(expression, context) {
+        return write_expression(f, expression, level);
+    }
+    if f.alternate() {
+        f.write_str("(\n")?;
+        write_indent(f, level + 1)?;
+        write_expression(f, expression, level + 1)?;
+        f.write_char('\n')?;
+        write_indent(f, level)?;
+        f.write_char(')')
+    } else {
+        f.write_char('(')?;
+        write_expression(f, expression, level)?;
+        f.write_char(')')
+    }
+}
+
+fn write_junction(
+    f: &mut Formatter<'_>,
+    expressions: &[Expression],
+    operator: char,
+    context: Context,
+    level: usize,
+) -> fmt::Result {
+    // There is no GSS for a constant, an empty group is the closest thing to one.
+    if expressions.is_empty() {
+        return f.write_str("()");
+    }
+    for (i, expression) in expressions.iter().enumerate() {
+        if i > 0 {
+            if f.alternate() {
+                f.write_char('\n')?;
+                write_indent(f, level)?;
+                write!(f, "{} ", operator)?;
+            } else {
+                write!(f, " {} ", operator)?;
+            }
+        }
+        write_operand(f, expression, context, level)?;
+    }
+    Ok(())
+}
+
+fn write_comparison(f: &mut Formatter<'_>, key: &str, operator: &str, target: &str) -> fmt::Result {
+    write_text(f, key)?;
+    write!(f, " {} ", operator)?;
+    write_text(f, target)
+}
+
+fn write_between(f: &mut Formatter<'_>, key: &str, bounds: &Bounds) -> fmt::Result {
+    write_text(f, key)?;
+    f.write_str(if bounds.lower_inclusive {
+        " .. ["
+    } else {
+        " .. ("
+    })?;
+    write_text(f, &bounds.lower)?;
+    f.write_str(", ")?;
+    write_text(f, &bounds.upper)?;
+    f.write_char(if bounds.upper_inclusive { ']' } else { ')' })
+}
+
+fn write_expression(f: &mut Formatter<'_>, expression: &Expression, level: usize) -> fmt::Result {
+    match &expression.node {
+        Node::All(expressions) => write_junction(f, expressions, '&', Context::All, level),
+        Node::AnyOf(expressions) => write_junction(f, expressions, '|', Context::AnyOf, level),
+        Node::Not(expression) => {
+            f.write_char('!')?;
+            write_operand(f, expression, Context::Not, level)
+        }
+        Node::Equal(key, target) => write_comparison(f, key, "=", target),
+        Node::EqualCI(key, target) => write_comparison(f, key, "~", target),
+        Node::Greater(key, target) => write_comparison(f, key, ">", target),
+        Node::Less(key, target) => write_comparison(f, key, "<", target),
+        Node::GreaterEqual(key, target) => write_comparison(f, key, ">=", target),
+        Node::LessEqual(key, target) => write_comparison(f, key, "<=", target),
+        Node::NotEqual(key, target) => write_comparison(f, key, "!=", target),
+        Node::Between(key, bounds) => write_between(f, key, bounds),
+        Node::Contains(key, target) => write_comparison(f, key, "*=", target),
+        Node::StartsWith(key, target) => write_comparison(f, key, "^=", target),
+        Node::EndsWith(key, target) => write_comparison(f, key, "$=", target),
+        Node::Wildcard(key, target) => write_comparison(f, key, "*", target),
+        Node::Regex(key, target) => write_comparison(f, key, "$", target),
+        Node::RegexCI(key, target) => write_comparison(f, key, "$~", target),
+        Node::Any(key, targets) => {
+            write_text(f, key)?;
+            f.write_str(" ? [")?;
+            for (i, target) in targets.iter().enumerate() {
+                if i > 0 {
+                    f.write_str(", ")?;
+                }
+                write_text(f, target)?;
+            }
+            f.write_char(']')
+        }
+        Node::Null(key) => {
+            write_text(f, key)?;
+            f.write_str(" -")
+        }
+    }
+}
+
+/// Renders the expression as GSS with as few groups as precedence allows.
+///
+/// The alternate form (`{:#}`) puts every operand of a group on its own line and indents
+/// nested groups. Both forms parse back into the same expression only if the expression could
+/// have come from the parser in the first place. GSS cannot spell the other shapes that serde
+/// or a [`Fold`](crate::visit::Fold) may build, and they parse back into an expression that
+/// matches the same values but is not equal:
+///
+/// - an `All` or `AnyOf` of a single expression prints as that expression,
+/// - an `All` directly in an `All`, or an `AnyOf` in an `AnyOf`, prints in parentheses, but
+///   the parser merges it into the outer group,
+/// - an empty `All` or `AnyOf` prints as `()`, which does not parse at all.
+impl Display for Expression {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write_operand(f, self, Context::Top, 0)
+    }
+}
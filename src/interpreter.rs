@@ -1,3 +1,5 @@
+use crate::Expression;
+
 #[cfg(feature = "evaluate")]
 pub mod evaluate;
 
@@ -10,5 +12,155 @@ pub mod postgres;
 #[cfg(feature = "sqlite")]
 pub mod sqlite;
 
+#[cfg(feature = "mssql")]
+pub mod mssql;
+
+#[cfg(feature = "clickhouse")]
+pub mod clickhouse;
+
+#[cfg(feature = "duckdb")]
+pub mod duckdb;
+
+#[cfg(any(
+    feature = "mysql",
+    feature = "postgres",
+    feature = "sqlite",
+    feature = "mssql",
+    feature = "clickhouse",
+    feature = "duckdb"
+))]
+mod sql;
+
 #[cfg(feature = "hasura")]
 pub mod hasura;
+
+/// What the SQL interpreters use for a key instead of a column named after it.
+#[cfg(any(
+    feature = "mysql",
+    feature = "postgres",
+    feature = "sqlite",
+    feature = "mssql",
+    feature = "clickhouse",
+    feature = "duckdb"
+))]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Rename {
+    /// A column, quoted as an identifier, with `.` separating its qualifiers, e.g. `t.name`.
+    Column(String),
+    /// SQL used as is, e.g. `LOWER(t.name)`, which must never come from user input.
+    Raw(String),
+}
+
+#[cfg(any(
+    feature = "mysql",
+    feature = "postgres",
+    feature = "sqlite",
+    feature = "mssql",
+    feature = "clickhouse",
+    feature = "duckdb"
+))]
+impl From<&str> for Rename {
+    fn from(column: &str) -> Self {
+        Self::Column(column.to_string())
+    }
+}
+
+#[cfg(any(
+    feature = "mysql",
+    feature = "postgres",
+    feature = "sqlite",
+    feature = "mssql",
+    feature = "clickhouse",
+    feature = "duckdb"
+))]
+impl From<String> for Rename {
+    fn from(column: String) -> Self {
+        Self::Column(column)
+    }
+}
+
+/// Adds `c` to a `LIKE` pattern with `ESCAPE '\'`, escaping it if it has a meaning there.
+#[cfg(any(
+    feature = "mysql",
+    feature = "postgres",
+    feature = "sqlite",
+    feature = "mssql",
+    feature = "clickhouse",
+    feature = "duckdb",
+    feature = "hasura"
+))]
+fn push_like_literal(like: &mut String, c: char) {
+    if matches!(c, '\\' | '%' | '_') {
+        like.push('\\');
+    }
+    like.push(c);
+}
+
+/// Escapes `\`, `%` and `_` with `\`, so a `LIKE` pattern matches them literally.
+#[cfg(any(
+    feature = "mysql",
+    feature = "postgres",
+    feature = "sqlite",
+    feature = "mssql",
+    feature = "clickhouse",
+    feature = "duckdb",
+    feature = "hasura"
+))]
+fn escape_like(s: &str) -> String {
+    let mut like = String::with_capacity(s.len());
+    for c in s.chars() {
+        push_like_literal(&mut like, c);
+    }
+    like
+}
+
+/// The `LIKE` pattern, with `ESCAPE '\'`, matching what a wildcard pattern does.
+#[cfg(any(
+    feature = "mysql",
+    feature = "postgres",
+    feature = "sqlite",
+    feature = "mssql",
+    feature = "clickhouse",
+    feature = "duckdb",
+    feature = "hasura"
+))]
+fn wildcard_to_like(pattern: &str) -> String {
+    let mut like = String::with_capacity(pattern.len());
+    for token in crate::wildcard::tokens(pattern) {
+        match token {
+            crate::wildcard::Token::Char(c) => push_like_literal(&mut like, c),
+            crate::wildcard::Token::AnyRun => like.push('%'),
+            crate::wildcard::Token::AnyChar => like.push('_'),
+        }
+    }
+    like
+}
+
+/// Somewhere an expression can run, e.g. in memory or as a SQL condition.
+///
+/// Every backend in this module has a struct implementing it, which forwards to the
+/// backend's `interpret` function, so the same call site works with any of them. Those are
+/// unit structs, except for the options of `mssql::Mssql`:
+///
+/// ```
+/// # use flp_gsp::{interpreter::Interpreter, Expression};
+/// fn run<I: Interpreter>(interpreter: &I, search: &str, context: I::Context<'_>) -> I::Output
+/// where
+///     I::Error: std::fmt::Debug,
+/// {
+///     let expression = Expression::try_from_str(search).unwrap();
+///     interpreter.interpret(&expression, context).unwrap()
+/// }
+/// ```
+pub trait Interpreter {
+    /// Whatever the backend needs besides the expression, e.g. the types of the keys.
+    type Context<'a>;
+    type Output;
+    type Error;
+
+    fn interpret(
+        &self,
+        expression: &Expression,
+        context: Self::Context<'_>,
+    ) -> Result<Self::Output, Self::Error>;
+}
@@ -0,0 +1,228 @@
+use chrono::{NaiveDate, NaiveDateTime, ParseError};
+use rust_decimal::Decimal;
+use std::{collections::HashMap, num::ParseFloatError, num::ParseIntError, str::ParseBoolError};
+use uuid::Uuid;
+
+use super::{
+    sql::{self, SqlDialect},
+    Rename,
+};
+use crate::Expression;
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("Cannot parse to int: {0}")]
+    ParseInt(#[from] ParseIntError),
+    #[error("Cannot parse to float: {0}")]
+    ParseFloat(#[from] ParseFloatError),
+    #[error("Cannot parse to bool: {0}")]
+    ParseBool(#[from] ParseBoolError),
+    #[error("Cannot parse to chrono: {0}")]
+    ParseChrono(#[from] ParseError),
+    #[error("Cannot parse to decimal: {0}")]
+    ParseDecimal(#[from] rust_decimal::Error),
+    #[error("Cannot parse to uuid: {0}")]
+    ParseUuid(#[from] uuid::Error),
+    #[error("Cannot parse a single value to an array")]
+    ArrayTarget,
+    #[error("Cannot find key {0} in types")]
+    UnknownKey(String),
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ClickhouseType {
+    /// The targets of an `Any`, which cannot be the type of a key.
+    Array(Vec<ClickhouseType>),
+    Bool(Option<bool>),
+    Date(Option<NaiveDate>),
+    DateTime(Option<NaiveDateTime>),
+    DateTime64(Option<NaiveDateTime>),
+    /// A `Decimal(precision, scale)` column, whose parameters have the same type whatever
+    /// the scale of the value.
+    Decimal {
+        precision: u32,
+        scale: u32,
+        value: Option<Decimal>,
+    },
+    Float32(Option<f32>),
+    Float64(Option<f64>),
+    Int8(Option<i8>),
+    Int16(Option<i16>),
+    Int32(Option<i32>),
+    Int64(Option<i64>),
+    StringLike(Option<String>),
+    UInt8(Option<u8>),
+    UInt16(Option<u16>),
+    UInt32(Option<u32>),
+    UInt64(Option<u64>),
+    Uuid(Option<Uuid>),
+}
+impl ClickhouseType {
+    pub fn replace_and_return(&self, s: &str) -> Result<Self> {
+        match self {
+            ClickhouseType::Array(_) => Err(Error::ArrayTarget),
+            ClickhouseType::Bool(_) => Ok(ClickhouseType::Bool(Some(s.parse()?))),
+            ClickhouseType::Date(_) => Ok(ClickhouseType::Date(Some(s.parse()?))),
+            ClickhouseType::DateTime(_) => Ok(ClickhouseType::DateTime(Some(s.parse()?))),
+            ClickhouseType::DateTime64(_) => Ok(ClickhouseType::DateTime64(Some(s.parse()?))),
+            ClickhouseType::Decimal {
+                precision, scale, ..
+            } => Ok(ClickhouseType::Decimal {
+                precision: *precision,
+                scale: *scale,
+                value: Some(s.parse()?),
+            }),
+            ClickhouseType::Float32(_) => Ok(ClickhouseType::Float32(Some(s.parse()?))),
+            ClickhouseType::Float64(_) => Ok(ClickhouseType::Float64(Some(s.parse()?))),
+            ClickhouseType::Int8(_) => Ok(ClickhouseType::Int8(Some(s.parse()?))),
+            ClickhouseType::Int16(_) => Ok(ClickhouseType::Int16(Some(s.parse()?))),
+            ClickhouseType::Int32(_) => Ok(ClickhouseType::Int32(Some(s.parse()?))),
+            ClickhouseType::Int64(_) => Ok(ClickhouseType::Int64(Some(s.parse()?))),
+            ClickhouseType::StringLike(_) => Ok(ClickhouseType::StringLike(Some(s.into()))),
+            ClickhouseType::UInt8(_) => Ok(ClickhouseType::UInt8(Some(s.parse()?))),
+            ClickhouseType::UInt16(_) => Ok(ClickhouseType::UInt16(Some(s.parse()?))),
+            ClickhouseType::UInt32(_) => Ok(ClickhouseType::UInt32(Some(s.parse()?))),
+            ClickhouseType::UInt64(_) => Ok(ClickhouseType::UInt64(Some(s.parse()?))),
+            ClickhouseType::Uuid(_) => Ok(ClickhouseType::Uuid(Some(s.parse()?))),
+        }
+    }
+
+    /// The name of the type in ClickHouse, as used in query parameters.
+    pub fn type_name(&self) -> String {
+        match self {
+            ClickhouseType::Array(values) => format!(
+                "Array({})",
+                values
+                    .first()
+                    .map(ClickhouseType::type_name)
+                    .unwrap_or_else(|| "Nothing".to_string())
+            ),
+            ClickhouseType::Bool(_) => "Bool".to_string(),
+            ClickhouseType::Date(_) => "Date".to_string(),
+            ClickhouseType::DateTime(_) => "DateTime".to_string(),
+            ClickhouseType::DateTime64(_) => "DateTime64(6)".to_string(),
+            ClickhouseType::Decimal {
+                precision, scale, ..
+            } => format!("Decimal({}, {})", precision, scale),
+            ClickhouseType::Float32(_) => "Float32".to_string(),
+            ClickhouseType::Float64(_) => "Float64".to_string(),
+            ClickhouseType::Int8(_) => "Int8".to_string(),
+            ClickhouseType::Int16(_) => "Int16".to_string(),
+            ClickhouseType::Int32(_) => "Int32".to_string(),
+            ClickhouseType::Int64(_) => "Int64".to_string(),
+            ClickhouseType::StringLike(_) => "String".to_string(),
+            ClickhouseType::UInt8(_) => "UInt8".to_string(),
+            ClickhouseType::UInt16(_) => "UInt16".to_string(),
+            ClickhouseType::UInt32(_) => "UInt32".to_string(),
+            ClickhouseType::UInt64(_) => "UInt64".to_string(),
+            ClickhouseType::Uuid(_) => "UUID".to_string(),
+        }
+    }
+}
+
+pub type ClickhouseRenames = HashMap<String, Rename>;
+pub type ClickhouseTypes = HashMap<String, ClickhouseType>;
+
+/// [`interpret`] naming the parameters from `p1`.
+pub fn interpret_expression(
+    expression: &Expression,
+    renames: &ClickhouseRenames,
+    types: &ClickhouseTypes,
+) -> Result<(String, Vec<ClickhouseType>)> {
+    interpret(expression, renames, types, 1)
+}
+
+/// The condition and its parameters, where the `i`th one is named `p{index + i}`, e.g.
+/// `{p1:Int32}`.
+pub fn interpret(
+    expression: &Expression,
+    renames: &ClickhouseRenames,
+    types: &ClickhouseTypes,
+    index: usize,
+) -> Result<(String, Vec<ClickhouseType>)> {
+    sql::interpret(&Clickhouse, expression, renames, types, index)
+}
+
+/// [`interpret`] as an [`Interpreter`](super::Interpreter), naming the parameters from the
+/// index in the context.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Clickhouse;
+
+impl super::Interpreter for Clickhouse {
+    type Context<'a> = (&'a ClickhouseRenames, &'a ClickhouseTypes, usize);
+    type Output = (String, Vec<ClickhouseType>);
+    type Error = Error;
+
+    fn interpret(
+        &self,
+        expression: &Expression,
+        (renames, types, index): Self::Context<'_>,
+    ) -> Result<(String, Vec<ClickhouseType>)> {
+        interpret(expression, renames, types, index)
+    }
+}
+
+impl SqlDialect for Clickhouse {
+    type Type = ClickhouseType;
+    type Error = Error;
+
+    // Quoted identifiers escape with `\`.
+    fn quote_identifier(&self, identifier: &str) -> String {
+        let mut quoted = String::with_capacity(identifier.len() + 2);
+        quoted.push('`');
+        for c in identifier.chars() {
+            if matches!(c, '`' | '\\') {
+                quoted.push('\\');
+            }
+            quoted.push(c);
+        }
+        quoted.push('`');
+        quoted
+    }
+
+    fn placeholder(&self, index: usize, value: &ClickhouseType) -> String {
+        format!("{{p{}:{}}}", index, value.type_name())
+    }
+
+    // `\` always escapes in `LIKE`, which has no `ESCAPE` clause.
+    fn like(&self, column: &str, placeholder: &str) -> String {
+        format!("{} LIKE {}", column, placeholder)
+    }
+
+    fn like_ci(&self, column: &str, placeholder: &str) -> String {
+        format!("{} ILIKE {}", column, placeholder)
+    }
+
+    fn regex(&self, column: &str, placeholder: &str, _case_insensitive: bool) -> Result<String> {
+        Ok(format!("match({}, {})", column, placeholder))
+    }
+
+    fn regex_target(&self, target: &str, case_insensitive: bool) -> String {
+        if case_insensitive {
+            format!("(?i){}", target)
+        } else {
+            target.to_string()
+        }
+    }
+
+    fn bind(&self, value_type: &ClickhouseType, target: &str) -> Result<ClickhouseType> {
+        value_type.replace_and_return(target)
+    }
+
+    fn array(
+        &self,
+        values: Vec<ClickhouseType>,
+    ) -> std::result::Result<ClickhouseType, Vec<ClickhouseType>> {
+        Ok(ClickhouseType::Array(values))
+    }
+
+    fn in_array(&self, column: &str, placeholder: &str) -> String {
+        format!("has({}, {})", placeholder, column)
+    }
+
+    fn unknown_key(&self, key: &str) -> Error {
+        Error::UnknownKey(key.to_string())
+    }
+}
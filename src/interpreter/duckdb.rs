@@ -0,0 +1,170 @@
+use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, ParseError, Utc};
+use rust_decimal::Decimal;
+use std::{collections::HashMap, num::ParseFloatError, num::ParseIntError, str::ParseBoolError};
+use uuid::Uuid;
+
+use super::{
+    sql::{self, quote_identifier, SqlDialect},
+    Rename,
+};
+use crate::Expression;
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("Cannot parse to int: {0}")]
+    ParseInt(#[from] ParseIntError),
+    #[error("Cannot parse to float: {0}")]
+    ParseFloat(#[from] ParseFloatError),
+    #[error("Cannot parse to bool: {0}")]
+    ParseBool(#[from] ParseBoolError),
+    #[error("Cannot parse to chrono: {0}")]
+    ParseChrono(#[from] ParseError),
+    #[error("Cannot parse to decimal: {0}")]
+    ParseDecimal(#[from] rust_decimal::Error),
+    #[error("Cannot parse to uuid: {0}")]
+    ParseUuid(#[from] uuid::Error),
+    #[error("Cannot parse a single value to a list")]
+    ListTarget,
+    #[error("Cannot find key {0} in types")]
+    UnknownKey(String),
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum DuckdbType {
+    BigInt(Option<i64>),
+    Blob(Option<Vec<u8>>),
+    Boolean(Option<bool>),
+    Date(Option<NaiveDate>),
+    Decimal(Option<Decimal>),
+    Double(Option<f64>),
+    Float(Option<f32>),
+    HugeInt(Option<i128>),
+    Integer(Option<i32>),
+    /// The targets of an `Any`, which cannot be the type of a key.
+    List(Vec<DuckdbType>),
+    SmallInt(Option<i16>),
+    StringLike(Option<String>),
+    Time(Option<NaiveTime>),
+    Timestamp(Option<NaiveDateTime>),
+    TimestampTz(Option<DateTime<Utc>>),
+    TinyInt(Option<i8>),
+    UBigInt(Option<u64>),
+    UInteger(Option<u32>),
+    USmallInt(Option<u16>),
+    UTinyInt(Option<u8>),
+    Uuid(Option<Uuid>),
+}
+impl DuckdbType {
+    pub fn replace_and_return(&self, s: &str) -> Result<Self> {
+        match self {
+            DuckdbType::BigInt(_) => Ok(DuckdbType::BigInt(Some(s.parse()?))),
+            DuckdbType::Blob(_) => Ok(DuckdbType::Blob(Some(s.as_bytes().into()))),
+            DuckdbType::Boolean(_) => Ok(DuckdbType::Boolean(Some(s.parse()?))),
+            DuckdbType::Date(_) => Ok(DuckdbType::Date(Some(s.parse()?))),
+            DuckdbType::Decimal(_) => Ok(DuckdbType::Decimal(Some(s.parse()?))),
+            DuckdbType::Double(_) => Ok(DuckdbType::Double(Some(s.parse()?))),
+            DuckdbType::Float(_) => Ok(DuckdbType::Float(Some(s.parse()?))),
+            DuckdbType::HugeInt(_) => Ok(DuckdbType::HugeInt(Some(s.parse()?))),
+            DuckdbType::Integer(_) => Ok(DuckdbType::Integer(Some(s.parse()?))),
+            DuckdbType::List(_) => Err(Error::ListTarget),
+            DuckdbType::SmallInt(_) => Ok(DuckdbType::SmallInt(Some(s.parse()?))),
+            DuckdbType::StringLike(_) => Ok(DuckdbType::StringLike(Some(s.into()))),
+            DuckdbType::Time(_) => Ok(DuckdbType::Time(Some(s.parse()?))),
+            DuckdbType::Timestamp(_) => Ok(DuckdbType::Timestamp(Some(s.parse()?))),
+            DuckdbType::TimestampTz(_) => Ok(DuckdbType::TimestampTz(Some(s.parse()?))),
+            DuckdbType::TinyInt(_) => Ok(DuckdbType::TinyInt(Some(s.parse()?))),
+            DuckdbType::UBigInt(_) => Ok(DuckdbType::UBigInt(Some(s.parse()?))),
+            DuckdbType::UInteger(_) => Ok(DuckdbType::UInteger(Some(s.parse()?))),
+            DuckdbType::USmallInt(_) => Ok(DuckdbType::USmallInt(Some(s.parse()?))),
+            DuckdbType::UTinyInt(_) => Ok(DuckdbType::UTinyInt(Some(s.parse()?))),
+            DuckdbType::Uuid(_) => Ok(DuckdbType::Uuid(Some(s.parse()?))),
+        }
+    }
+}
+
+pub type DuckdbRenames = HashMap<String, Rename>;
+pub type DuckdbTypes = HashMap<String, DuckdbType>;
+
+/// [`interpret`] numbering the placeholders from `$1`.
+pub fn interpret_expression(
+    expression: &Expression,
+    renames: &DuckdbRenames,
+    types: &DuckdbTypes,
+) -> Result<(String, Vec<DuckdbType>)> {
+    interpret(expression, renames, types, 1)
+}
+
+pub fn interpret(
+    expression: &Expression,
+    renames: &DuckdbRenames,
+    types: &DuckdbTypes,
+    index: usize,
+) -> Result<(String, Vec<DuckdbType>)> {
+    sql::interpret(&Duckdb, expression, renames, types, index)
+}
+
+/// [`interpret`] as an [`Interpreter`](super::Interpreter), numbering the placeholders from
+/// the index in the context.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Duckdb;
+
+impl super::Interpreter for Duckdb {
+    type Context<'a> = (&'a DuckdbRenames, &'a DuckdbTypes, usize);
+    type Output = (String, Vec<DuckdbType>);
+    type Error = Error;
+
+    fn interpret(
+        &self,
+        expression: &Expression,
+        (renames, types, index): Self::Context<'_>,
+    ) -> Result<(String, Vec<DuckdbType>)> {
+        interpret(expression, renames, types, index)
+    }
+}
+
+impl SqlDialect for Duckdb {
+    type Type = DuckdbType;
+    type Error = Error;
+
+    fn quote_identifier(&self, identifier: &str) -> String {
+        quote_identifier(identifier, '"', '"')
+    }
+
+    fn placeholder(&self, index: usize, _value: &DuckdbType) -> String {
+        format!("${}", index)
+    }
+
+    fn like(&self, column: &str, placeholder: &str) -> String {
+        format!("{} LIKE {} ESCAPE '\\'", column, placeholder)
+    }
+
+    fn like_ci(&self, column: &str, placeholder: &str) -> String {
+        format!("{} ILIKE {} ESCAPE '\\'", column, placeholder)
+    }
+
+    fn regex(&self, column: &str, placeholder: &str, case_insensitive: bool) -> Result<String> {
+        Ok(if case_insensitive {
+            format!("regexp_matches({}, {}, 'i')", column, placeholder)
+        } else {
+            format!("regexp_matches({}, {})", column, placeholder)
+        })
+    }
+
+    fn bind(&self, value_type: &DuckdbType, target: &str) -> Result<DuckdbType> {
+        value_type.replace_and_return(target)
+    }
+
+    fn array(&self, values: Vec<DuckdbType>) -> std::result::Result<DuckdbType, Vec<DuckdbType>> {
+        Ok(DuckdbType::List(values))
+    }
+
+    fn in_array(&self, column: &str, placeholder: &str) -> String {
+        format!("list_contains({}, {})", placeholder, column)
+    }
+
+    fn unknown_key(&self, key: &str) -> Error {
+        Error::UnknownKey(key.to_string())
+    }
+}
@@ -1,16 +1,28 @@
-use regex::Regex;
-use std::collections::HashMap;
-use wildmatch::WildMatch;
+use regex::{Regex, RegexBuilder};
+use std::{collections::HashMap, convert::Infallible};
 
-use crate::{Expression, Node};
+use crate::{wildcard, Bounds, Expression, Node};
 
 pub struct EvaluateRule {
     pub is_equal: fn(value: &str, target: &str) -> bool,
     pub is_equal_ci: fn(value: &str, target: &str) -> bool,
     pub is_greater_than: fn(value: &str, target: &str) -> bool,
     pub is_less_than: fn(value: &str, target: &str) -> bool,
+    /// `is_greater_than || is_equal` when not set.
+    pub is_greater_than_or_equal: Option<fn(value: &str, target: &str) -> bool>,
+    /// `is_less_than || is_equal` when not set.
+    pub is_less_than_or_equal: Option<fn(value: &str, target: &str) -> bool>,
+    /// `!is_equal` when not set.
+    pub is_not_equal: Option<fn(value: &str, target: &str) -> bool>,
+    /// `is_greater_than` or `is_equal` the lower bound, and `is_less_than` or `is_equal` the
+    /// upper bound, as far as each is inclusive, when not set.
+    pub is_between: Option<fn(value: &str, bounds: &Bounds) -> bool>,
+    pub is_containing: fn(value: &str, target: &str) -> bool,
+    pub is_starting_with: fn(value: &str, target: &str) -> bool,
+    pub is_ending_with: fn(value: &str, target: &str) -> bool,
     pub is_match_wildcard: fn(value: &str, target: &str) -> bool,
     pub is_match_regex: fn(value: &str, target: &str) -> bool,
+    pub is_match_regex_ci: fn(value: &str, target: &str) -> bool,
     pub is_in: fn(value: &str, target: &[String]) -> bool,
     pub is_none: fn(value: &str) -> bool,
 }
@@ -21,7 +33,14 @@ impl Default for EvaluateRule {
             is_equal_ci: |value, target| value.to_lowercase() == target.to_lowercase(),
             is_greater_than: |value, target| value > target,
             is_less_than: |value, target| value < target,
-            is_match_wildcard: |value, target| WildMatch::new(target).matches(value),
+            is_greater_than_or_equal: None,
+            is_less_than_or_equal: None,
+            is_not_equal: None,
+            is_between: None,
+            is_containing: |value, target| value.contains(target),
+            is_starting_with: |value, target| value.starts_with(target),
+            is_ending_with: |value, target| value.ends_with(target),
+            is_match_wildcard: |value, target| wildcard::matches(target, value),
             is_match_regex: |value, target| {
                 let reg = Regex::new(target);
                 if reg.is_err() {
@@ -30,9 +49,17 @@ impl Default for EvaluateRule {
                 let reg = reg.unwrap();
                 reg.is_match(value)
             },
+            is_match_regex_ci: |value, target| {
+                let reg = RegexBuilder::new(target).case_insensitive(true).build();
+                if reg.is_err() {
+                    return false;
+                }
+                let reg = reg.unwrap();
+                reg.is_match(value)
+            },
             is_in: |value, target| target.contains(&value.to_string()),
             is_none: |value| {
-                value.to_ascii_lowercase() == "none" || value.to_ascii_lowercase() == "null"
+                value.eq_ignore_ascii_case("none") || value.eq_ignore_ascii_case("null")
             },
         }
     }
@@ -47,12 +74,12 @@ pub fn interpret_expression(
     pairs: &EvaluatePairs,
 ) -> bool {
     match &expression.node {
-        Node::And(left, right) => {
-            interpret_expression(left, rules, pairs) && interpret_expression(right, rules, pairs)
-        }
-        Node::Or(left, right) => {
-            interpret_expression(left, rules, pairs) || interpret_expression(right, rules, pairs)
-        }
+        Node::All(expressions) => expressions
+            .iter()
+            .all(|expression| interpret_expression(expression, rules, pairs)),
+        Node::AnyOf(expressions) => expressions
+            .iter()
+            .any(|expression| interpret_expression(expression, rules, pairs)),
         Node::Not(expr) => !interpret_expression(expr, rules, pairs),
         Node::Equal(key, target) => {
             let rule = rules.get(key);
@@ -106,6 +133,115 @@ pub fn interpret_expression(
             let value = value.unwrap();
             (rule.is_less_than)(value, target)
         }
+        Node::GreaterEqual(key, target) => {
+            let rule = rules.get(key);
+            if rule.is_none() {
+                return false;
+            }
+            let rule = rule.unwrap();
+            let value = pairs.get(key);
+            if value.is_none() {
+                return false;
+            }
+            let value = value.unwrap();
+            match rule.is_greater_than_or_equal {
+                Some(is_greater_than_or_equal) => is_greater_than_or_equal(value, target),
+                None => (rule.is_greater_than)(value, target) || (rule.is_equal)(value, target),
+            }
+        }
+        Node::LessEqual(key, target) => {
+            let rule = rules.get(key);
+            if rule.is_none() {
+                return false;
+            }
+            let rule = rule.unwrap();
+            let value = pairs.get(key);
+            if value.is_none() {
+                return false;
+            }
+            let value = value.unwrap();
+            match rule.is_less_than_or_equal {
+                Some(is_less_than_or_equal) => is_less_than_or_equal(value, target),
+                None => (rule.is_less_than)(value, target) || (rule.is_equal)(value, target),
+            }
+        }
+        Node::NotEqual(key, target) => {
+            let rule = rules.get(key);
+            if rule.is_none() {
+                return false;
+            }
+            let rule = rule.unwrap();
+            let value = pairs.get(key);
+            if value.is_none() {
+                return false;
+            }
+            let value = value.unwrap();
+            match rule.is_not_equal {
+                Some(is_not_equal) => is_not_equal(value, target),
+                None => !(rule.is_equal)(value, target),
+            }
+        }
+        Node::Between(key, bounds) => {
+            let rule = rules.get(key);
+            if rule.is_none() {
+                return false;
+            }
+            let rule = rule.unwrap();
+            let value = pairs.get(key);
+            if value.is_none() {
+                return false;
+            }
+            let value = value.unwrap();
+            match rule.is_between {
+                Some(is_between) => is_between(value, bounds),
+                None => {
+                    let above = (rule.is_greater_than)(value, &bounds.lower)
+                        || (bounds.lower_inclusive && (rule.is_equal)(value, &bounds.lower));
+                    let below = (rule.is_less_than)(value, &bounds.upper)
+                        || (bounds.upper_inclusive && (rule.is_equal)(value, &bounds.upper));
+                    above && below
+                }
+            }
+        }
+        Node::Contains(key, target) => {
+            let rule = rules.get(key);
+            if rule.is_none() {
+                return false;
+            }
+            let rule = rule.unwrap();
+            let value = pairs.get(key);
+            if value.is_none() {
+                return false;
+            }
+            let value = value.unwrap();
+            (rule.is_containing)(value, target)
+        }
+        Node::StartsWith(key, target) => {
+            let rule = rules.get(key);
+            if rule.is_none() {
+                return false;
+            }
+            let rule = rule.unwrap();
+            let value = pairs.get(key);
+            if value.is_none() {
+                return false;
+            }
+            let value = value.unwrap();
+            (rule.is_starting_with)(value, target)
+        }
+        Node::EndsWith(key, target) => {
+            let rule = rules.get(key);
+            if rule.is_none() {
+                return false;
+            }
+            let rule = rule.unwrap();
+            let value = pairs.get(key);
+            if value.is_none() {
+                return false;
+            }
+            let value = value.unwrap();
+            (rule.is_ending_with)(value, target)
+        }
         Node::Wildcard(key, target) => {
             let rule = rules.get(key);
             if rule.is_none() {
@@ -132,6 +268,19 @@ pub fn interpret_expression(
             let value = value.unwrap();
             (rule.is_match_regex)(value, target)
         }
+        Node::RegexCI(key, target) => {
+            let rule = rules.get(key);
+            if rule.is_none() {
+                return false;
+            }
+            let rule = rule.unwrap();
+            let value = pairs.get(key);
+            if value.is_none() {
+                return false;
+            }
+            let value = value.unwrap();
+            (rule.is_match_regex_ci)(value, target)
+        }
         Node::Any(key, targets) => {
             let rule = rules.get(key);
             if rule.is_none() {
@@ -164,3 +313,21 @@ pub fn interpret_expression(
 pub fn interpret(expression: &Expression, rules: &EvaluateRules, pairs: &EvaluatePairs) -> bool {
     interpret_expression(expression, rules, pairs)
 }
+
+/// [`interpret`] as an [`Interpreter`](super::Interpreter), which never fails.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Evaluate;
+
+impl super::Interpreter for Evaluate {
+    type Context<'a> = (&'a EvaluateRules, &'a EvaluatePairs);
+    type Output = bool;
+    type Error = Infallible;
+
+    fn interpret(
+        &self,
+        expression: &Expression,
+        (rules, pairs): Self::Context<'_>,
+    ) -> std::result::Result<bool, Infallible> {
+        Ok(interpret(expression, rules, pairs))
+    }
+}
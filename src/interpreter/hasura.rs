@@ -1,5 +1,6 @@
 use std::{collections::HashMap, num::ParseFloatError, num::ParseIntError, str::ParseBoolError};
 
+use super::{escape_like, wildcard_to_like};
 use crate::{Expression, Node};
 
 #[derive(Debug, thiserror::Error)]
@@ -29,26 +30,47 @@ impl HasuraType {
             HasuraType::Boolean => Ok(s.parse::<bool>()?.to_string()),
             HasuraType::Integer => Ok(s.parse::<i64>()?.to_string()),
             HasuraType::Float => Ok(s.parse::<f64>()?.to_string()),
-            HasuraType::StringLike => Ok(format!("\"{}\"", s)),
+            HasuraType::StringLike => Ok(quote(s)),
         }
     }
 }
 
+/// `s` as a GraphQL string, escaping what cannot appear in one as it is.
+fn quote(s: &str) -> String {
+    let mut quoted = String::with_capacity(s.len() + 2);
+    quoted.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c if c.is_control() => quoted.push_str(&format!("\\u{:04x}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
 pub type HasuraTypes = HashMap<String, HasuraType>;
 
+fn interpret_expressions(expressions: &[Expression], types: &HasuraTypes) -> Result<String> {
+    let mut clauses = Vec::with_capacity(expressions.len());
+    for expression in expressions {
+        clauses.push(interpret_expression(expression, types)?);
+    }
+    Ok(clauses.join(","))
+}
+
 pub fn interpret_expression(expression: &Expression, types: &HasuraTypes) -> Result<String> {
     Ok(match &expression.node {
-        Node::And(left, right) => {
-            let left_clause = interpret_expression(left, types)?;
-            let right_clause = interpret_expression(right, types)?;
-            let clause = format!("{{_and:[{},{}]}}", left_clause, right_clause);
-            clause
+        Node::All(expressions) => {
+            format!("{{_and:[{}]}}", interpret_expressions(expressions, types)?)
         }
-        Node::Or(left, right) => {
-            let left_clause = interpret_expression(left, types)?;
-            let right_clause = interpret_expression(right, types)?;
-            let clause = format!("{{_or:[{},{}]}}", left_clause, right_clause);
-            clause
+        Node::AnyOf(expressions) => {
+            format!("{{_or:[{}]}}", interpret_expressions(expressions, types)?)
         }
         Node::Not(expr) => {
             let clause = interpret_expression(expr, types)?;
@@ -68,7 +90,7 @@ pub fn interpret_expression(expression: &Expression, types: &HasuraTypes) -> Res
             types
                 .get(key)
                 .ok_or(Error::UnknownKey(key.to_string()))?
-                .to_hasura_string(target)?
+                .to_hasura_string(&escape_like(target))?
         ),
         Node::Greater(key, target) => format!(
             "{{{}:{{_gt:{}}}}}",
@@ -86,13 +108,80 @@ pub fn interpret_expression(expression: &Expression, types: &HasuraTypes) -> Res
                 .ok_or(Error::UnknownKey(key.to_string()))?
                 .to_hasura_string(target)?
         ),
+        Node::GreaterEqual(key, target) => format!(
+            "{{{}:{{_gte:{}}}}}",
+            key,
+            types
+                .get(key)
+                .ok_or(Error::UnknownKey(key.to_string()))?
+                .to_hasura_string(target)?
+        ),
+        Node::LessEqual(key, target) => format!(
+            "{{{}:{{_lte:{}}}}}",
+            key,
+            types
+                .get(key)
+                .ok_or(Error::UnknownKey(key.to_string()))?
+                .to_hasura_string(target)?
+        ),
+        Node::NotEqual(key, target) => format!(
+            "{{{}:{{_neq:{}}}}}",
+            key,
+            types
+                .get(key)
+                .ok_or(Error::UnknownKey(key.to_string()))?
+                .to_hasura_string(target)?
+        ),
+        Node::Between(key, bounds) => {
+            let value_type = types.get(key).ok_or(Error::UnknownKey(key.to_string()))?;
+            format!(
+                "{{{}:{{{}:{},{}:{}}}}}",
+                key,
+                if bounds.lower_inclusive {
+                    "_gte"
+                } else {
+                    "_gt"
+                },
+                value_type.to_hasura_string(&bounds.lower)?,
+                if bounds.upper_inclusive {
+                    "_lte"
+                } else {
+                    "_lt"
+                },
+                value_type.to_hasura_string(&bounds.upper)?
+            )
+        }
+        Node::Contains(key, target) => format!(
+            "{{{}:{{_like:{}}}}}",
+            key,
+            types
+                .get(key)
+                .ok_or(Error::UnknownKey(key.to_string()))?
+                .to_hasura_string(&format!("%{}%", escape_like(target)))?
+        ),
+        Node::StartsWith(key, target) => format!(
+            "{{{}:{{_like:{}}}}}",
+            key,
+            types
+                .get(key)
+                .ok_or(Error::UnknownKey(key.to_string()))?
+                .to_hasura_string(&format!("{}%", escape_like(target)))?
+        ),
+        Node::EndsWith(key, target) => format!(
+            "{{{}:{{_like:{}}}}}",
+            key,
+            types
+                .get(key)
+                .ok_or(Error::UnknownKey(key.to_string()))?
+                .to_hasura_string(&format!("%{}", escape_like(target)))?
+        ),
         Node::Wildcard(key, target) => format!(
-            "{{{}:{{_ilike:{}}}}}",
+            "{{{}:{{_like:{}}}}}",
             key,
             types
                 .get(key)
                 .ok_or(Error::UnknownKey(key.to_string()))?
-                .to_hasura_string(&target.replace("*", "%").replace("?", "_"))?
+                .to_hasura_string(&wildcard_to_like(target))?
         ),
         Node::Regex(key, target) => format!(
             "{{{}:{{_regex:{}}}}}",
@@ -102,6 +191,14 @@ pub fn interpret_expression(expression: &Expression, types: &HasuraTypes) -> Res
                 .ok_or(Error::UnknownKey(key.to_string()))?
                 .to_hasura_string(target)?
         ),
+        Node::RegexCI(key, target) => format!(
+            "{{{}:{{_iregex:{}}}}}",
+            key,
+            types
+                .get(key)
+                .ok_or(Error::UnknownKey(key.to_string()))?
+                .to_hasura_string(target)?
+        ),
         Node::Any(key, targets) => {
             let mut values = Vec::with_capacity(targets.len());
             for target in targets.iter() {
@@ -124,5 +221,19 @@ pub fn interpret_expression(expression: &Expression, types: &HasuraTypes) -> Res
 }
 
 pub fn interpret(expression: &Expression, types: &HasuraTypes) -> Result<String> {
-    Ok(interpret_expression(expression, types)?)
+    interpret_expression(expression, types)
+}
+
+/// [`interpret`] as an [`Interpreter`](super::Interpreter).
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Hasura;
+
+impl super::Interpreter for Hasura {
+    type Context<'a> = &'a HasuraTypes;
+    type Output = String;
+    type Error = Error;
+
+    fn interpret(&self, expression: &Expression, types: Self::Context<'_>) -> Result<String> {
+        interpret(expression, types)
+    }
 }
@@ -0,0 +1,187 @@
+use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, ParseError, Utc};
+use rust_decimal::Decimal;
+use std::{collections::HashMap, num::ParseFloatError, num::ParseIntError, str::ParseBoolError};
+use uuid::Uuid;
+
+use super::{
+    sql::{self, quote_identifier, SqlDialect},
+    Rename,
+};
+use crate::Expression;
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("Cannot parse to int: {0}")]
+    ParseInt(#[from] ParseIntError),
+    #[error("Cannot parse to float: {0}")]
+    ParseFloat(#[from] ParseFloatError),
+    #[error("Cannot parse to bool: {0}")]
+    ParseBool(#[from] ParseBoolError),
+    #[error("Cannot parse to chrono: {0}")]
+    ParseChrono(#[from] ParseError),
+    #[error("Cannot parse to decimal: {0}")]
+    ParseDecimal(#[from] rust_decimal::Error),
+    #[error("Cannot parse to uuid: {0}")]
+    ParseUuid(#[from] uuid::Error),
+    #[error("Cannot find key {0} in types")]
+    UnknownKey(String),
+    #[error("{0} is not supported")]
+    Unsupported(String),
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum MssqlType {
+    BigInt(Option<i64>),
+    Bit(Option<bool>),
+    Date(Option<NaiveDate>),
+    DateTime2(Option<NaiveDateTime>),
+    DateTimeOffset(Option<DateTime<Utc>>),
+    Decimal(Option<Decimal>),
+    Float(Option<f64>),
+    Int(Option<i32>),
+    Real(Option<f32>),
+    SmallInt(Option<i16>),
+    StringLike(Option<String>),
+    Time(Option<NaiveTime>),
+    TinyInt(Option<u8>),
+    UniqueIdentifier(Option<Uuid>),
+    VarBinary(Option<Vec<u8>>),
+}
+impl MssqlType {
+    pub fn replace_and_return(&self, s: &str) -> Result<Self> {
+        match self {
+            MssqlType::BigInt(_) => Ok(MssqlType::BigInt(Some(s.parse()?))),
+            MssqlType::Bit(_) => Ok(MssqlType::Bit(Some(s.parse()?))),
+            MssqlType::Date(_) => Ok(MssqlType::Date(Some(s.parse()?))),
+            MssqlType::DateTime2(_) => Ok(MssqlType::DateTime2(Some(s.parse()?))),
+            MssqlType::DateTimeOffset(_) => Ok(MssqlType::DateTimeOffset(Some(s.parse()?))),
+            MssqlType::Decimal(_) => Ok(MssqlType::Decimal(Some(s.parse()?))),
+            MssqlType::Float(_) => Ok(MssqlType::Float(Some(s.parse()?))),
+            MssqlType::Int(_) => Ok(MssqlType::Int(Some(s.parse()?))),
+            MssqlType::Real(_) => Ok(MssqlType::Real(Some(s.parse()?))),
+            MssqlType::SmallInt(_) => Ok(MssqlType::SmallInt(Some(s.parse()?))),
+            MssqlType::StringLike(_) => Ok(MssqlType::StringLike(Some(s.into()))),
+            MssqlType::Time(_) => Ok(MssqlType::Time(Some(s.parse()?))),
+            MssqlType::TinyInt(_) => Ok(MssqlType::TinyInt(Some(s.parse()?))),
+            MssqlType::UniqueIdentifier(_) => Ok(MssqlType::UniqueIdentifier(Some(s.parse()?))),
+            MssqlType::VarBinary(_) => Ok(MssqlType::VarBinary(Some(s.as_bytes().into()))),
+        }
+    }
+}
+
+pub type MssqlRenames = HashMap<String, Rename>;
+pub type MssqlTypes = HashMap<String, MssqlType>;
+
+/// The collation that case-insensitive comparisons use, whatever the column has.
+pub const CASE_INSENSITIVE_COLLATION: &str = "Latin1_General_CI_AS";
+
+/// The collation that the case-sensitive `LIKE` of `Contains`, `StartsWith`, `EndsWith` and
+/// `Wildcard` uses, whatever the column has.
+pub const CASE_SENSITIVE_COLLATION: &str = "Latin1_General_CS_AS";
+
+/// [`interpret`] numbering the placeholders from `@p1`.
+pub fn interpret_expression(
+    expression: &Expression,
+    renames: &MssqlRenames,
+    types: &MssqlTypes,
+) -> Result<(String, Vec<MssqlType>)> {
+    interpret(expression, renames, types, 1)
+}
+
+/// The condition for `expression`, numbering the placeholders from `@p{index}`. A regex
+/// fails with [`Error::Unsupported`], see [`Mssql::regexp_like`].
+pub fn interpret(
+    expression: &Expression,
+    renames: &MssqlRenames,
+    types: &MssqlTypes,
+    index: usize,
+) -> Result<(String, Vec<MssqlType>)> {
+    sql::interpret(&Mssql::default(), expression, renames, types, index)
+}
+
+/// [`interpret`] as an [`Interpreter`](super::Interpreter), numbering the placeholders from
+/// the index in the context.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Mssql {
+    /// Whether `Regex` and `RegexCI` may use `REGEXP_LIKE`, which needs SQL Server 2025 or
+    /// Azure SQL. They fail with [`Error::Unsupported`] otherwise.
+    pub regexp_like: bool,
+}
+
+impl super::Interpreter for Mssql {
+    type Context<'a> = (&'a MssqlRenames, &'a MssqlTypes, usize);
+    type Output = (String, Vec<MssqlType>);
+    type Error = Error;
+
+    fn interpret(
+        &self,
+        expression: &Expression,
+        (renames, types, index): Self::Context<'_>,
+    ) -> Result<(String, Vec<MssqlType>)> {
+        sql::interpret(self, expression, renames, types, index)
+    }
+}
+
+impl SqlDialect for Mssql {
+    type Type = MssqlType;
+    type Error = Error;
+
+    fn quote_identifier(&self, identifier: &str) -> String {
+        quote_identifier(identifier, '[', ']')
+    }
+
+    fn placeholder(&self, index: usize, _value: &MssqlType) -> String {
+        format!("@p{}", index)
+    }
+
+    // There are no boolean literals in T-SQL.
+    fn boolean(&self, value: bool) -> &'static str {
+        if value {
+            "1=1"
+        } else {
+            "1=0"
+        }
+    }
+
+    // `[` starts a character class.
+    fn like_pattern(&self, pattern: String, _case_insensitive: bool) -> String {
+        pattern.replace('[', "\\[")
+    }
+
+    fn like(&self, column: &str, placeholder: &str) -> String {
+        format!(
+            "{} COLLATE {} LIKE {} ESCAPE '\\'",
+            column, CASE_SENSITIVE_COLLATION, placeholder
+        )
+    }
+
+    fn like_ci(&self, column: &str, placeholder: &str) -> String {
+        format!(
+            "{} COLLATE {} LIKE {} ESCAPE '\\'",
+            column, CASE_INSENSITIVE_COLLATION, placeholder
+        )
+    }
+
+    fn regex(&self, column: &str, placeholder: &str, case_insensitive: bool) -> Result<String> {
+        if !self.regexp_like {
+            return Err(Error::Unsupported(
+                "Regex without Mssql::regexp_like".to_string(),
+            ));
+        }
+        Ok(if case_insensitive {
+            format!("REGEXP_LIKE({}, {}, 'i')", column, placeholder)
+        } else {
+            format!("REGEXP_LIKE({}, {})", column, placeholder)
+        })
+    }
+
+    fn bind(&self, value_type: &MssqlType, target: &str) -> Result<MssqlType> {
+        value_type.replace_and_return(target)
+    }
+
+    fn unknown_key(&self, key: &str) -> Error {
+        Error::UnknownKey(key.to_string())
+    }
+}
@@ -2,7 +2,11 @@ use chrono::{DateTime, Local, NaiveDate, NaiveDateTime, NaiveTime, ParseError, U
 use rust_decimal::Decimal;
 use std::{collections::HashMap, num::ParseFloatError, num::ParseIntError, str::ParseBoolError};
 
-use crate::{Expression, Node};
+use super::{
+    sql::{self, quote_identifier, SqlDialect},
+    Rename,
+};
+use crate::Expression;
 
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
@@ -74,7 +78,7 @@ impl MysqlType {
     }
 }
 
-pub type MysqlRenames = HashMap<String, String>;
+pub type MysqlRenames = HashMap<String, Rename>;
 pub type MysqlTypes = HashMap<String, MysqlType>;
 
 pub fn interpret_expression(
@@ -82,98 +86,7 @@ pub fn interpret_expression(
     renames: &MysqlRenames,
     types: &MysqlTypes,
 ) -> Result<(String, Vec<MysqlType>)> {
-    Ok(match &expression.node {
-        Node::And(left, right) => {
-            let (left_clause, mut left_types) = interpret_expression(left, renames, types)?;
-            let (right_clause, mut right_types) = interpret_expression(right, renames, types)?;
-            let clause = format!("({} AND {})", left_clause, right_clause);
-            left_types.append(&mut right_types);
-            (clause, left_types)
-        }
-        Node::Or(left, right) => {
-            let (left_clause, mut left_types) = interpret_expression(left, renames, types)?;
-            let (right_clause, mut right_types) = interpret_expression(right, renames, types)?;
-            let clause = format!("({} OR {})", left_clause, right_clause);
-            left_types.append(&mut right_types);
-            (clause, left_types)
-        }
-        Node::Not(expr) => {
-            let (clause, types) = interpret_expression(expr, renames, types)?;
-            (format!("(NOT {})", clause), types)
-        }
-        Node::Equal(key, target) => (
-            format!("{} = ?", renames.get(key).unwrap_or_else(|| key)),
-            vec![types
-                .get(key)
-                .ok_or(Error::UnknownKey(key.to_string()))?
-                .replace_and_return(target)?],
-        ),
-        Node::EqualCI(key, target) => (
-            format!("{} LIKE ?", renames.get(key).unwrap_or_else(|| key)),
-            vec![types
-                .get(key)
-                .ok_or(Error::UnknownKey(key.to_string()))?
-                .replace_and_return(target)?],
-        ),
-        Node::Greater(key, target) => (
-            format!("{} > ?", renames.get(key).unwrap_or_else(|| key)),
-            vec![types
-                .get(key)
-                .ok_or(Error::UnknownKey(key.to_string()))?
-                .replace_and_return(target)?],
-        ),
-        Node::Less(key, target) => (
-            format!("{} < ?", renames.get(key).unwrap_or_else(|| key)),
-            vec![types
-                .get(key)
-                .ok_or(Error::UnknownKey(key.to_string()))?
-                .replace_and_return(target)?],
-        ),
-        Node::Wildcard(key, target) => (
-            format!("{} LIKE ?", renames.get(key).unwrap_or_else(|| key)),
-            vec![types
-                .get(key)
-                .ok_or(Error::UnknownKey(key.to_string()))?
-                .replace_and_return(&target.replace("*", "%").replace("?", "_"))?],
-        ),
-        Node::Regex(key, target) => (
-            format!("{} = ?", renames.get(key).unwrap_or_else(|| key)),
-            vec![types
-                .get(key)
-                .ok_or(Error::UnknownKey(key.to_string()))?
-                .replace_and_return(target)?],
-        ),
-        Node::Any(key, targets) => {
-            let sql = if targets.is_empty() {
-                "FALSE".to_string()
-            } else {
-                format!(
-                    "{} IN ({})",
-                    renames.get(key).unwrap_or_else(|| key),
-                    targets.iter().map(|_| "?").collect::<Vec<_>>().join(", ")
-                )
-            };
-            let mut binds = Vec::with_capacity(targets.len());
-            for target in targets.iter() {
-                binds.push(
-                    types
-                        .get(key)
-                        .ok_or(Error::UnknownKey(key.to_string()))?
-                        .replace_and_return(target)?,
-                );
-            }
-            (sql, binds)
-        }
-        Node::Null(key) => {
-            if !types.contains_key(key) {
-                return Err(Error::UnknownKey(key.to_string()));
-            }
-            (
-                format!("{} IS NULL", renames.get(key).unwrap_or_else(|| key)),
-                vec![],
-            )
-        }
-    })
+    sql::interpret(&Mysql, expression, renames, types, 1)
 }
 
 pub fn interpret(
@@ -181,5 +94,57 @@ pub fn interpret(
     renames: &MysqlRenames,
     types: &MysqlTypes,
 ) -> Result<(String, Vec<MysqlType>)> {
-    Ok(interpret_expression(expression, renames, types)?)
+    interpret_expression(expression, renames, types)
+}
+
+/// [`interpret`] as an [`Interpreter`](super::Interpreter).
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Mysql;
+
+impl super::Interpreter for Mysql {
+    type Context<'a> = (&'a MysqlRenames, &'a MysqlTypes);
+    type Output = (String, Vec<MysqlType>);
+    type Error = Error;
+
+    fn interpret(
+        &self,
+        expression: &Expression,
+        (renames, types): Self::Context<'_>,
+    ) -> Result<(String, Vec<MysqlType>)> {
+        interpret(expression, renames, types)
+    }
+}
+
+impl SqlDialect for Mysql {
+    type Type = MysqlType;
+    type Error = Error;
+
+    fn quote_identifier(&self, identifier: &str) -> String {
+        quote_identifier(identifier, '`', '`')
+    }
+
+    fn placeholder(&self, _index: usize, _value: &MysqlType) -> String {
+        "?".to_string()
+    }
+
+    // Case follows the collation of the column, which by default ignores it.
+    fn like(&self, column: &str, placeholder: &str) -> String {
+        format!("{} LIKE {} ESCAPE '\\\\'", column, placeholder)
+    }
+
+    fn regex(&self, column: &str, placeholder: &str, case_insensitive: bool) -> Result<String> {
+        Ok(if case_insensitive {
+            format!("REGEXP_LIKE({}, {}, 'i')", column, placeholder)
+        } else {
+            format!("{} REGEXP {}", column, placeholder)
+        })
+    }
+
+    fn bind(&self, value_type: &MysqlType, target: &str) -> Result<MysqlType> {
+        value_type.replace_and_return(target)
+    }
+
+    fn unknown_key(&self, key: &str) -> Error {
+        Error::UnknownKey(key.to_string())
+    }
 }
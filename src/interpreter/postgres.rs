@@ -5,7 +5,11 @@ use rust_decimal::Decimal;
 use std::{collections::HashMap, num::ParseFloatError, num::ParseIntError, str::ParseBoolError};
 use uuid::Uuid;
 
-use crate::{Expression, Node};
+use super::{
+    sql::{self, quote_identifier, SqlDialect},
+    Rename,
+};
+use crate::Expression;
 
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
@@ -79,106 +83,17 @@ impl PostgresType {
     }
 }
 
-pub type PostgresRenames = HashMap<String, String>;
+pub type PostgresRenames = HashMap<String, Rename>;
 pub type PostgresTypes = HashMap<String, PostgresType>;
 
+/// [`interpret`] leaving every placeholder as `??`, for the caller to number.
 pub fn interpret_expression(
     expression: &Expression,
     renames: &PostgresRenames,
     types: &PostgresTypes,
 ) -> Result<(String, Vec<PostgresType>)> {
-    Ok(match &expression.node {
-        Node::And(left, right) => {
-            let (left_clause, mut left_types) = interpret_expression(left, renames, types)?;
-            let (right_clause, mut right_types) = interpret_expression(right, renames, types)?;
-            let clause = format!("({} AND {})", left_clause, right_clause);
-            left_types.append(&mut right_types);
-            (clause, left_types)
-        }
-        Node::Or(left, right) => {
-            let (left_clause, mut left_types) = interpret_expression(left, renames, types)?;
-            let (right_clause, mut right_types) = interpret_expression(right, renames, types)?;
-            let clause = format!("({} OR {})", left_clause, right_clause);
-            left_types.append(&mut right_types);
-            (clause, left_types)
-        }
-        Node::Not(expr) => {
-            let (clause, types) = interpret_expression(expr, renames, types)?;
-            (format!("(NOT {})", clause), types)
-        }
-        Node::Equal(key, target) => (
-            format!("{} = ??", renames.get(key).unwrap_or_else(|| key)),
-            vec![types
-                .get(key)
-                .ok_or(Error::UnknownKey(key.to_string()))?
-                .replace_and_return(target)?],
-        ),
-        Node::EqualCI(key, target) => (
-            format!("{} ILIKE ??", renames.get(key).unwrap_or_else(|| key)),
-            vec![types
-                .get(key)
-                .ok_or(Error::UnknownKey(key.to_string()))?
-                .replace_and_return(target)?],
-        ),
-        Node::Greater(key, target) => (
-            format!("{} > ??", renames.get(key).unwrap_or_else(|| key)),
-            vec![types
-                .get(key)
-                .ok_or(Error::UnknownKey(key.to_string()))?
-                .replace_and_return(target)?],
-        ),
-        Node::Less(key, target) => (
-            format!("{} < ??", renames.get(key).unwrap_or_else(|| key)),
-            vec![types
-                .get(key)
-                .ok_or(Error::UnknownKey(key.to_string()))?
-                .replace_and_return(target)?],
-        ),
-        Node::Wildcard(key, target) => (
-            format!("{} ILIKE ??", renames.get(key).unwrap_or_else(|| key)),
-            vec![types
-                .get(key)
-                .ok_or(Error::UnknownKey(key.to_string()))?
-                .replace_and_return(&target.replace("*", "%").replace("?", "_"))?],
-        ),
-        Node::Regex(key, target) => (
-            format!("{} = ??", renames.get(key).unwrap_or_else(|| key)),
-            vec![types
-                .get(key)
-                .ok_or(Error::UnknownKey(key.to_string()))?
-                .replace_and_return(target)?],
-        ),
-        Node::Any(key, targets) => {
-            let sql = if targets.is_empty() {
-                "FALSE".to_string()
-            } else {
-                format!(
-                    "{} IN ({})",
-                    renames.get(key).unwrap_or_else(|| key),
-                    targets.iter().map(|_| "??").collect::<Vec<_>>().join(", ")
-                )
-            };
-            let mut binds = Vec::with_capacity(targets.len());
-            for target in targets.iter() {
-                binds.push(
-                    types
-                        .get(key)
-                        .ok_or(Error::UnknownKey(key.to_string()))?
-                        .replace_and_return(target)?,
-                );
-            }
-            (sql, binds)
-        }
-        Node::Null(key) => {
-            if !types.contains_key(key) {
-                return Err(Error::UnknownKey(key.to_string()));
-            }
-            (
-                format!("{} IS NULL", renames.get(key).unwrap_or_else(|| key)),
-                vec![],
-            )
-        }
-    })
+    let dialect = Dialect { numbered: false };
+    sql::interpret(&dialect, expression, renames, types, 0)
 }
 
 pub fn interpret(
@@ -187,14 +102,73 @@ pub fn interpret(
     types: &PostgresTypes,
     index: usize,
 ) -> Result<(String, Vec<PostgresType>)> {
-    let (tmp_sql, params) = interpret_expression(expression, renames, types)?;
-    let mut buffer = String::new();
-    let splitted = tmp_sql.split("??").collect::<Vec<_>>();
-    for (i, s) in splitted.iter().enumerate() {
-        buffer.push_str(s);
-        if i < splitted.len() - 1 {
-            buffer.push_str(format!("${}", index + i).as_str());
+    let dialect = Dialect { numbered: true };
+    sql::interpret(&dialect, expression, renames, types, index)
+}
+
+/// [`interpret`] as an [`Interpreter`](super::Interpreter), numbering the placeholders from
+/// the index in the context.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Postgres;
+
+impl super::Interpreter for Postgres {
+    type Context<'a> = (&'a PostgresRenames, &'a PostgresTypes, usize);
+    type Output = (String, Vec<PostgresType>);
+    type Error = Error;
+
+    fn interpret(
+        &self,
+        expression: &Expression,
+        (renames, types, index): Self::Context<'_>,
+    ) -> Result<(String, Vec<PostgresType>)> {
+        interpret(expression, renames, types, index)
+    }
+}
+
+/// The dialect of both functions, which only differ in their placeholders.
+struct Dialect {
+    /// Whether placeholders are `$1`, `$2`, ..., rather than `??`.
+    numbered: bool,
+}
+
+impl SqlDialect for Dialect {
+    type Type = PostgresType;
+    type Error = Error;
+
+    fn quote_identifier(&self, identifier: &str) -> String {
+        quote_identifier(identifier, '"', '"')
+    }
+
+    fn placeholder(&self, index: usize, _value: &PostgresType) -> String {
+        if self.numbered {
+            format!("${}", index)
+        } else {
+            "??".to_string()
         }
     }
-    Ok((buffer, params))
+
+    fn like(&self, column: &str, placeholder: &str) -> String {
+        format!("{} LIKE {} ESCAPE '\\'", column, placeholder)
+    }
+
+    fn like_ci(&self, column: &str, placeholder: &str) -> String {
+        format!("{} ILIKE {} ESCAPE '\\'", column, placeholder)
+    }
+
+    fn regex(&self, column: &str, placeholder: &str, case_insensitive: bool) -> Result<String> {
+        Ok(format!(
+            "{} {} {}",
+            column,
+            if case_insensitive { "~*" } else { "~" },
+            placeholder
+        ))
+    }
+
+    fn bind(&self, value_type: &PostgresType, target: &str) -> Result<PostgresType> {
+        value_type.replace_and_return(target)
+    }
+
+    fn unknown_key(&self, key: &str) -> Error {
+        Error::UnknownKey(key.to_string())
+    }
 }
@@ -0,0 +1,271 @@
+//! The SQL generator behind every SQL interpreter, which only differ in their [`SqlDialect`].
+
+use std::collections::HashMap;
+
+use super::{escape_like, wildcard_to_like, Rename};
+use crate::{Bounds, Expression, Node};
+
+/// How a database spells the parts of a condition that are not standard SQL.
+pub(crate) trait SqlDialect {
+    /// A bind value, whose variant also tells how to parse the targets of a key.
+    type Type;
+    type Error;
+
+    /// `identifier` quoted so that nothing in it is read as SQL.
+    fn quote_identifier(&self, identifier: &str) -> String;
+
+    /// The placeholder for `value`, the `index`th bind value counting from the index the
+    /// caller started at.
+    fn placeholder(&self, index: usize, value: &Self::Type) -> String;
+
+    /// The condition that is always `value`, e.g. for an empty group.
+    fn boolean(&self, value: bool) -> &'static str {
+        if value {
+            "TRUE"
+        } else {
+            "FALSE"
+        }
+    }
+
+    /// The pattern to bind for [`like`](Self::like), or [`like_ci`](Self::like_ci) when
+    /// `case_insensitive`, given as a `LIKE` pattern in which `\`, `%` and `_` are already
+    /// escaped, for a dialect that gives a meaning to more characters or matches otherwise.
+    fn like_pattern(&self, pattern: String, _case_insensitive: bool) -> String {
+        pattern
+    }
+
+    /// `column` matching the `LIKE` pattern `placeholder`, in which `\` escapes, as
+    /// case-sensitively as the dialect allows.
+    fn like(&self, column: &str, placeholder: &str) -> String;
+
+    /// The same ignoring case, for case-insensitive equality.
+    fn like_ci(&self, column: &str, placeholder: &str) -> String {
+        self.like(column, placeholder)
+    }
+
+    /// `column` matching the regex `placeholder`, or an error for a dialect that cannot
+    /// match a regex.
+    fn regex(
+        &self,
+        column: &str,
+        placeholder: &str,
+        case_insensitive: bool,
+    ) -> Result<String, Self::Error>;
+
+    /// The regex to bind for `target`.
+    fn regex_target(&self, target: &str, _case_insensitive: bool) -> String {
+        target.to_string()
+    }
+
+    fn bind(&self, value_type: &Self::Type, target: &str) -> Result<Self::Type, Self::Error>;
+
+    /// One bind value holding all of `values`, the targets of `Any`, or `values` as they are
+    /// for a dialect that binds them one by one.
+    fn array(&self, values: Vec<Self::Type>) -> Result<Self::Type, Vec<Self::Type>> {
+        Err(values)
+    }
+
+    /// `column` being an element of the array `placeholder`.
+    fn in_array(&self, column: &str, placeholder: &str) -> String {
+        format!("{} = ANY({})", column, placeholder)
+    }
+
+    fn unknown_key(&self, key: &str) -> Self::Error;
+}
+
+/// `identifier` between `open` and `close`, doubling any `close` in it so it cannot end early.
+#[cfg(any(
+    feature = "mysql",
+    feature = "postgres",
+    feature = "sqlite",
+    feature = "mssql",
+    feature = "duckdb"
+))]
+pub(crate) fn quote_identifier(identifier: &str, open: char, close: char) -> String {
+    let mut quoted = String::with_capacity(identifier.len() + 2);
+    quoted.push(open);
+    for c in identifier.chars() {
+        if c == close {
+            quoted.push(close);
+        }
+        quoted.push(c);
+    }
+    quoted.push(close);
+    quoted
+}
+
+struct Generator<'a, D: SqlDialect> {
+    dialect: &'a D,
+    renames: &'a HashMap<String, Rename>,
+    types: &'a HashMap<String, D::Type>,
+    binds: Vec<D::Type>,
+    index: usize,
+}
+
+impl<'a, D: SqlDialect> Generator<'a, D> {
+    fn value_type(&self, key: &str) -> Result<&'a D::Type, D::Error> {
+        self.types
+            .get(key)
+            .ok_or_else(|| self.dialect.unknown_key(key))
+    }
+
+    /// The column of `key`, which needs a type so that undeclared keys never reach the SQL.
+    fn column(&self, key: &str) -> Result<String, D::Error> {
+        self.value_type(key)?;
+        Ok(match self.renames.get(key) {
+            Some(Rename::Column(column)) => column
+                .split('.')
+                .map(|part| self.dialect.quote_identifier(part))
+                .collect::<Vec<_>>()
+                .join("."),
+            Some(Rename::Raw(sql)) => sql.clone(),
+            None => self.dialect.quote_identifier(key),
+        })
+    }
+
+    /// Binds `target` as a value of `key`, returning its placeholder.
+    fn bind(&mut self, key: &str, target: &str) -> Result<String, D::Error> {
+        let value = self.dialect.bind(self.value_type(key)?, target)?;
+        Ok(self.push(value))
+    }
+
+    fn push(&mut self, value: D::Type) -> String {
+        let placeholder = self.dialect.placeholder(self.index, &value);
+        self.binds.push(value);
+        self.index += 1;
+        placeholder
+    }
+
+    fn compare(&mut self, key: &str, operator: &str, target: &str) -> Result<String, D::Error> {
+        let column = self.column(key)?;
+        let placeholder = self.bind(key, target)?;
+        Ok(format!("{} {} {}", column, operator, placeholder))
+    }
+
+    fn like(&mut self, key: &str, pattern: String) -> Result<String, D::Error> {
+        let column = self.column(key)?;
+        let placeholder = self.bind(key, &self.dialect.like_pattern(pattern, false))?;
+        Ok(self.dialect.like(&column, &placeholder))
+    }
+
+    fn like_ci(&mut self, key: &str, pattern: String) -> Result<String, D::Error> {
+        let column = self.column(key)?;
+        let placeholder = self.bind(key, &self.dialect.like_pattern(pattern, true))?;
+        Ok(self.dialect.like_ci(&column, &placeholder))
+    }
+
+    fn regex(
+        &mut self,
+        key: &str,
+        target: &str,
+        case_insensitive: bool,
+    ) -> Result<String, D::Error> {
+        let column = self.column(key)?;
+        let placeholder = self.bind(key, &self.dialect.regex_target(target, case_insensitive))?;
+        self.dialect.regex(&column, &placeholder, case_insensitive)
+    }
+
+    fn between(&mut self, key: &str, bounds: &Bounds) -> Result<String, D::Error> {
+        let column = self.column(key)?;
+        let lower = self.bind(key, &bounds.lower)?;
+        let upper = self.bind(key, &bounds.upper)?;
+        Ok(if bounds.lower_inclusive && bounds.upper_inclusive {
+            format!("{} BETWEEN {} AND {}", column, lower, upper)
+        } else {
+            format!(
+                "({} {} {} AND {} {} {})",
+                column,
+                if bounds.lower_inclusive { ">=" } else { ">" },
+                lower,
+                column,
+                if bounds.upper_inclusive { "<=" } else { "<" },
+                upper
+            )
+        })
+    }
+
+    fn any(&mut self, key: &str, targets: &[String]) -> Result<String, D::Error> {
+        let column = self.column(key)?;
+        if targets.is_empty() {
+            return Ok(self.dialect.boolean(false).to_string());
+        }
+        let value_type = self.value_type(key)?;
+        let mut values = Vec::with_capacity(targets.len());
+        for target in targets {
+            values.push(self.dialect.bind(value_type, target)?);
+        }
+        Ok(match self.dialect.array(values) {
+            Ok(array) => {
+                let placeholder = self.push(array);
+                self.dialect.in_array(&column, &placeholder)
+            }
+            Err(values) => {
+                let placeholders = values
+                    .into_iter()
+                    .map(|value| self.push(value))
+                    .collect::<Vec<_>>();
+                format!("{} IN ({})", column, placeholders.join(", "))
+            }
+        })
+    }
+
+    fn expressions(
+        &mut self,
+        expressions: &[Expression],
+        operator: &str,
+        empty: bool,
+    ) -> Result<String, D::Error> {
+        if expressions.is_empty() {
+            return Ok(self.dialect.boolean(empty).to_string());
+        }
+        let mut clauses = Vec::with_capacity(expressions.len());
+        for expression in expressions {
+            clauses.push(self.expression(expression)?);
+        }
+        Ok(format!("({})", clauses.join(&format!(" {} ", operator))))
+    }
+
+    fn expression(&mut self, expression: &Expression) -> Result<String, D::Error> {
+        match &expression.node {
+            Node::All(expressions) => self.expressions(expressions, "AND", true),
+            Node::AnyOf(expressions) => self.expressions(expressions, "OR", false),
+            Node::Not(expression) => Ok(format!("(NOT {})", self.expression(expression)?)),
+            Node::Equal(key, target) => self.compare(key, "=", target),
+            Node::EqualCI(key, target) => self.like_ci(key, escape_like(target)),
+            Node::Greater(key, target) => self.compare(key, ">", target),
+            Node::Less(key, target) => self.compare(key, "<", target),
+            Node::GreaterEqual(key, target) => self.compare(key, ">=", target),
+            Node::LessEqual(key, target) => self.compare(key, "<=", target),
+            Node::NotEqual(key, target) => self.compare(key, "<>", target),
+            Node::Between(key, bounds) => self.between(key, bounds),
+            Node::Contains(key, target) => self.like(key, format!("%{}%", escape_like(target))),
+            Node::StartsWith(key, target) => self.like(key, format!("{}%", escape_like(target))),
+            Node::EndsWith(key, target) => self.like(key, format!("%{}", escape_like(target))),
+            Node::Wildcard(key, target) => self.like(key, wildcard_to_like(target)),
+            Node::Regex(key, target) => self.regex(key, target, false),
+            Node::RegexCI(key, target) => self.regex(key, target, true),
+            Node::Any(key, targets) => self.any(key, targets),
+            Node::Null(key) => Ok(format!("{} IS NULL", self.column(key)?)),
+        }
+    }
+}
+
+/// The condition for `expression` in `dialect` and its bind values, numbering the
+/// placeholders from `index`.
+pub(crate) fn interpret<D: SqlDialect>(
+    dialect: &D,
+    expression: &Expression,
+    renames: &HashMap<String, Rename>,
+    types: &HashMap<String, D::Type>,
+    index: usize,
+) -> Result<(String, Vec<D::Type>), D::Error> {
+    let mut generator = Generator {
+        dialect,
+        renames,
+        types,
+        binds: Vec::new(),
+        index,
+    };
+    let clause = generator.expression(expression)?;
+    Ok((clause, generator.binds))
+}
@@ -1,7 +1,11 @@
 use chrono::{DateTime, ParseError, Utc};
 use std::{collections::HashMap, num::ParseFloatError, num::ParseIntError, str::ParseBoolError};
 
-use crate::{Expression, Node};
+use super::{
+    sql::{self, quote_identifier, SqlDialect},
+    Rename,
+};
+use crate::Expression;
 
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
@@ -43,7 +47,22 @@ impl SqliteType {
     }
 }
 
-pub type SqliteRenames = HashMap<String, String>;
+#[cfg(feature = "rusqlite")]
+impl rusqlite::ToSql for SqliteType {
+    fn to_sql(&self) -> rusqlite::Result<rusqlite::types::ToSqlOutput<'_>> {
+        match self {
+            SqliteType::BigInt(v) => v.to_sql(),
+            SqliteType::Blob(v) => v.to_sql(),
+            SqliteType::Boolean(v) => v.to_sql(),
+            SqliteType::DateTime(v) => v.to_sql(),
+            SqliteType::Integer(v) => v.to_sql(),
+            SqliteType::Real(v) => v.to_sql(),
+            SqliteType::Text(v) => v.to_sql(),
+        }
+    }
+}
+
+pub type SqliteRenames = HashMap<String, Rename>;
 pub type SqliteTypes = HashMap<String, SqliteType>;
 
 pub fn interpret_expression(
@@ -51,98 +70,7 @@ pub fn interpret_expression(
     renames: &SqliteRenames,
     types: &SqliteTypes,
 ) -> Result<(String, Vec<SqliteType>)> {
-    Ok(match &expression.node {
-        Node::And(left, right) => {
-            let (left_clause, mut left_types) = interpret_expression(left, renames, types)?;
-            let (right_clause, mut right_types) = interpret_expression(right, renames, types)?;
-            let clause = format!("({} AND {})", left_clause, right_clause);
-            left_types.append(&mut right_types);
-            (clause, left_types)
-        }
-        Node::Or(left, right) => {
-            let (left_clause, mut left_types) = interpret_expression(left, renames, types)?;
-            let (right_clause, mut right_types) = interpret_expression(right, renames, types)?;
-            let clause = format!("({} OR {})", left_clause, right_clause);
-            left_types.append(&mut right_types);
-            (clause, left_types)
-        }
-        Node::Not(expr) => {
-            let (clause, types) = interpret_expression(expr, renames, types)?;
-            (format!("(NOT {})", clause), types)
-        }
-        Node::Equal(key, target) => (
-            format!("{} = ?", renames.get(key).unwrap_or_else(|| key)),
-            vec![types
-                .get(key)
-                .ok_or(Error::UnknownKey(key.to_string()))?
-                .replace_and_return(target)?],
-        ),
-        Node::EqualCI(key, target) => (
-            format!("{} LIKE ?", renames.get(key).unwrap_or_else(|| key)),
-            vec![types
-                .get(key)
-                .ok_or(Error::UnknownKey(key.to_string()))?
-                .replace_and_return(target)?],
-        ),
-        Node::Greater(key, target) => (
-            format!("{} > ?", renames.get(key).unwrap_or_else(|| key)),
-            vec![types
-                .get(key)
-                .ok_or(Error::UnknownKey(key.to_string()))?
-                .replace_and_return(target)?],
-        ),
-        Node::Less(key, target) => (
-            format!("{} < ?", renames.get(key).unwrap_or_else(|| key)),
-            vec![types
-                .get(key)
-                .ok_or(Error::UnknownKey(key.to_string()))?
-                .replace_and_return(target)?],
-        ),
-        Node::Wildcard(key, target) => (
-            format!("{} LIKE ?", renames.get(key).unwrap_or_else(|| key)),
-            vec![types
-                .get(key)
-                .ok_or(Error::UnknownKey(key.to_string()))?
-                .replace_and_return(&target.replace("*", "%").replace("?", "_"))?],
-        ),
-        Node::Regex(key, target) => (
-            format!("{} = ?", renames.get(key).unwrap_or_else(|| key)),
-            vec![types
-                .get(key)
-                .ok_or(Error::UnknownKey(key.to_string()))?
-                .replace_and_return(target)?],
-        ),
-        Node::Any(key, targets) => {
-            let sql = if targets.is_empty() {
-                "FALSE".to_string()
-            } else {
-                format!(
-                    "{} IN ({})",
-                    renames.get(key).unwrap_or_else(|| key),
-                    targets.iter().map(|_| "?").collect::<Vec<_>>().join(", ")
-                )
-            };
-            let mut binds = Vec::with_capacity(targets.len());
-            for target in targets.iter() {
-                binds.push(
-                    types
-                        .get(key)
-                        .ok_or(Error::UnknownKey(key.to_string()))?
-                        .replace_and_return(target)?,
-                );
-            }
-            (sql, binds)
-        }
-        Node::Null(key) => {
-            if !types.contains_key(key) {
-                return Err(Error::UnknownKey(key.to_string()));
-            }
-            (
-                format!("{} IS NULL", renames.get(key).unwrap_or_else(|| key)),
-                vec![],
-            )
-        }
-    })
+    sql::interpret(&Sqlite, expression, renames, types, 1)
 }
 
 pub fn interpret(
@@ -150,5 +78,131 @@ pub fn interpret(
     renames: &SqliteRenames,
     types: &SqliteTypes,
 ) -> Result<(String, Vec<SqliteType>)> {
-    Ok(interpret_expression(expression, renames, types)?)
+    interpret_expression(expression, renames, types)
+}
+
+/// Registers the `regexp()` function that SQLite calls for `REGEXP`, which it does not
+/// define itself, matching the same way as the `evaluate` interpreter. It is `NULL` when
+/// either side is, and an error when the pattern is not a valid regex.
+#[cfg(feature = "rusqlite")]
+pub fn register_regexp(connection: &rusqlite::Connection) -> rusqlite::Result<()> {
+    use rusqlite::{functions::FunctionFlags, types::ValueRef};
+
+    connection.create_scalar_function(
+        "regexp",
+        2,
+        FunctionFlags::SQLITE_UTF8 | FunctionFlags::SQLITE_DETERMINISTIC,
+        |ctx| {
+            if ctx.get_raw(0) == ValueRef::Null {
+                return Ok(None);
+            }
+            let regex = ctx.get_or_create_aux(0, |pattern| {
+                regex::Regex::new(pattern.as_str()?)
+                    .map_err(|err| rusqlite::Error::UserFunctionError(Box::new(err)))
+            })?;
+            let value: Option<String> = ctx.get(1)?;
+            Ok(value.map(|value| regex.is_match(&value)))
+        },
+    )
+}
+
+/// The `GLOB` pattern matching what the `LIKE` pattern `like`, with `ESCAPE '\'`, does.
+fn like_to_glob(like: &str) -> String {
+    let mut glob = String::with_capacity(like.len());
+    let mut chars = like.chars();
+    while let Some(c) = chars.next() {
+        let literal = match c {
+            '%' => {
+                glob.push('*');
+                continue;
+            }
+            '_' => {
+                glob.push('?');
+                continue;
+            }
+            '\\' => match chars.next() {
+                Some(c) => c,
+                None => break,
+            },
+            c => c,
+        };
+        // `GLOB` has no escape character, a class of one character stands for it instead.
+        match literal {
+            '*' | '?' | '[' => {
+                glob.push('[');
+                glob.push(literal);
+                glob.push(']');
+            }
+            c => glob.push(c),
+        }
+    }
+    glob
+}
+
+/// [`interpret`] as an [`Interpreter`](super::Interpreter).
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Sqlite;
+
+impl super::Interpreter for Sqlite {
+    type Context<'a> = (&'a SqliteRenames, &'a SqliteTypes);
+    type Output = (String, Vec<SqliteType>);
+    type Error = Error;
+
+    fn interpret(
+        &self,
+        expression: &Expression,
+        (renames, types): Self::Context<'_>,
+    ) -> Result<(String, Vec<SqliteType>)> {
+        interpret(expression, renames, types)
+    }
+}
+
+impl SqlDialect for Sqlite {
+    type Type = SqliteType;
+    type Error = Error;
+
+    fn quote_identifier(&self, identifier: &str) -> String {
+        quote_identifier(identifier, '"', '"')
+    }
+
+    fn placeholder(&self, _index: usize, _value: &SqliteType) -> String {
+        "?".to_string()
+    }
+
+    // `LIKE` ignores the case of ASCII letters, `GLOB` does not.
+    fn like_pattern(&self, pattern: String, case_insensitive: bool) -> String {
+        if case_insensitive {
+            pattern
+        } else {
+            like_to_glob(&pattern)
+        }
+    }
+
+    fn like(&self, column: &str, placeholder: &str) -> String {
+        format!("{} GLOB {}", column, placeholder)
+    }
+
+    fn like_ci(&self, column: &str, placeholder: &str) -> String {
+        format!("{} LIKE {} ESCAPE '\\'", column, placeholder)
+    }
+
+    fn regex(&self, column: &str, placeholder: &str, _case_insensitive: bool) -> Result<String> {
+        Ok(format!("{} REGEXP {}", column, placeholder))
+    }
+
+    fn regex_target(&self, target: &str, case_insensitive: bool) -> String {
+        if case_insensitive {
+            format!("(?i){}", target)
+        } else {
+            target.to_string()
+        }
+    }
+
+    fn bind(&self, value_type: &SqliteType, target: &str) -> Result<SqliteType> {
+        value_type.replace_and_return(target)
+    }
+
+    fn unknown_key(&self, key: &str) -> Error {
+        Error::UnknownKey(key.to_string())
+    }
 }
@@ -1,31 +1,72 @@
 #![forbid(unsafe_code)]
 
+pub mod analysis;
+pub mod builder;
+mod canonical;
+mod display;
 pub mod interpreter;
+mod normal_form;
 mod parser;
+#[cfg(feature = "serde")]
+pub mod serialization;
+mod simplify;
+pub mod visit;
+mod wildcard;
 
 use parser::comparison::Comparison;
 use parser::relation::Relation;
 
-#[derive(Debug)]
+pub use normal_form::NormalFormError;
+pub use parser::{
+    context::ParseLimits,
+    error::{Limit, ParseError, ParseErrorKind},
+};
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub enum Node {
-    And(Box<Expression>, Box<Expression>),
-    Or(Box<Expression>, Box<Expression>),
+    /// True when every expression is, so an empty list is always true.
+    All(Vec<Expression>),
+    /// True when at least one expression is, so an empty list is always false.
+    AnyOf(Vec<Expression>),
     Not(Box<Expression>),
     Equal(String, String),
     EqualCI(String, String),
     Greater(String, String),
     Less(String, String),
+    GreaterEqual(String, String),
+    LessEqual(String, String),
+    NotEqual(String, String),
+    Between(String, Bounds),
+    Contains(String, String),
+    StartsWith(String, String),
+    EndsWith(String, String),
     Wildcard(String, String),
     Regex(String, String),
+    RegexCI(String, String),
     Any(String, Vec<String>),
     Null(String),
 }
 
-#[derive(Debug)]
+/// Range of a [`Node::Between`].
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Bounds {
+    pub lower: String,
+    pub lower_inclusive: bool,
+    pub upper: String,
+    pub upper_inclusive: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct Expression {
     pub node: Node,
 }
 
+impl From<Node> for Expression {
+    fn from(node: Node) -> Self {
+        Self { node }
+    }
+}
+
 impl From<Comparison> for Expression {
     fn from(c: Comparison) -> Self {
         match c {
@@ -41,12 +82,44 @@ impl From<Comparison> for Expression {
             Comparison::IsLess(c) => Self {
                 node: Node::Less(c.left.0, c.right.0),
             },
+            Comparison::IsGreaterEqual(c) => Self {
+                node: Node::GreaterEqual(c.left.0, c.right.0),
+            },
+            Comparison::IsLessEqual(c) => Self {
+                node: Node::LessEqual(c.left.0, c.right.0),
+            },
+            Comparison::IsNotEqual(c) => Self {
+                node: Node::NotEqual(c.left.0, c.right.0),
+            },
+            Comparison::IsBetween(c) => Self {
+                node: Node::Between(
+                    c.left.0,
+                    Bounds {
+                        lower: c.right.lower,
+                        lower_inclusive: c.right.lower_inclusive,
+                        upper: c.right.upper,
+                        upper_inclusive: c.right.upper_inclusive,
+                    },
+                ),
+            },
+            Comparison::IsContains(c) => Self {
+                node: Node::Contains(c.left.0, c.right.0),
+            },
+            Comparison::IsStartsWith(c) => Self {
+                node: Node::StartsWith(c.left.0, c.right.0),
+            },
+            Comparison::IsEndsWith(c) => Self {
+                node: Node::EndsWith(c.left.0, c.right.0),
+            },
             Comparison::IsWildcard(c) => Self {
                 node: Node::Wildcard(c.left.0, c.right.0),
             },
             Comparison::IsRegex(c) => Self {
                 node: Node::Regex(c.left.0, c.right.0),
             },
+            Comparison::IsRegexCI(c) => Self {
+                node: Node::RegexCI(c.left.0, c.right.0),
+            },
             Comparison::IsAny(c) => Self {
                 node: Node::Any(c.left.0, c.right.0),
             },
@@ -57,49 +130,69 @@ impl From<Comparison> for Expression {
     }
 }
 
-impl From<Box<Relation>> for Expression {
-    fn from(relation: Box<Relation>) -> Self {
-        match *relation {
+impl From<Relation> for Expression {
+    fn from(relation: Relation) -> Self {
+        match relation {
             Relation::C(c) => c.into(),
-            Relation::RAR { left, right } => Self {
-                node: Node::And(Box::new(left.into()), Box::new(right.into())),
-            },
-            Relation::RAC { left, right } => Self {
-                node: Node::And(Box::new(left.into()), Box::new(right.into())),
-            },
-            Relation::CAR { left, right } => Self {
-                node: Node::And(Box::new(left.into()), Box::new(right.into())),
-            },
-            Relation::CAC { left, right } => Self {
-                node: Node::And(Box::new(left.into()), Box::new(right.into())),
-            },
-            Relation::ROR { left, right } => Self {
-                node: Node::Or(Box::new(left.into()), Box::new(right.into())),
-            },
-            Relation::ROC { left, right } => Self {
-                node: Node::Or(Box::new(left.into()), Box::new(right.into())),
-            },
-            Relation::COR { left, right } => Self {
-                node: Node::Or(Box::new(left.into()), Box::new(right.into())),
-            },
-            Relation::COC { left, right } => Self {
-                node: Node::Or(Box::new(left.into()), Box::new(right.into())),
-            },
-            Relation::NR(r) => Self {
-                node: Node::Not(Box::new(r.into())),
-            },
-            Relation::NC(c) => Self {
-                node: Node::Not(Box::new(c.into())),
+            Relation::And(relations) => {
+                let mut expressions = Vec::with_capacity(relations.len());
+                for relation in relations {
+                    // `(a & b) & c` is the same as `a & b & c`
+                    match Expression::from(relation) {
+                        Self {
+                            node: Node::All(mut nested),
+                        } => expressions.append(&mut nested),
+                        expression => expressions.push(expression),
+                    }
+                }
+                Self {
+                    node: Node::All(expressions),
+                }
+            }
+            Relation::Or(relations) => {
+                let mut expressions = Vec::with_capacity(relations.len());
+                for relation in relations {
+                    match Expression::from(relation) {
+                        Self {
+                            node: Node::AnyOf(mut nested),
+                        } => expressions.append(&mut nested),
+                        expression => expressions.push(expression),
+                    }
+                }
+                Self {
+                    node: Node::AnyOf(expressions),
+                }
+            }
+            Relation::Not(r) => Self {
+                node: Node::Not(Box::new((*r).into())),
             },
         }
     }
 }
 
+/// Options for [`Expression::try_from_str_with`].
+#[derive(Clone, Debug, Default)]
+pub struct ParseOptions {
+    /// Stop after the first complete relation and ignore whatever follows it, which is how
+    /// parsing worked before trailing input was rejected.
+    pub lenient: bool,
+    pub limits: ParseLimits,
+}
+
 impl Expression {
-    pub fn try_from_str(s: &str) -> Result<Self, String> {
-        Ok(parser::relation::relation(s)
-            .map_err(|err| err.to_string())?
-            .1
-            .into())
+    /// Parses a GSS string, which must be consumed entirely apart from trailing whitespace,
+    /// within the default [`ParseLimits`].
+    pub fn try_from_str(s: &str) -> Result<Self, ParseError> {
+        Self::try_from_str_with(s, &ParseOptions::default())
+    }
+
+    pub fn try_from_str_with(s: &str, options: &ParseOptions) -> Result<Self, ParseError> {
+        match parser::search(s, options.lenient, &options.limits) {
+            Ok((_, relation)) => Ok((*relation).into()),
+            Err(nom::Err::Error(err) | nom::Err::Failure(err)) => Err(ParseError::new(s, err)),
+            Err(nom::Err::Incomplete(_)) => {
+                unreachable!("complete parsers never ask for more input")
+            }
+        }
     }
 }
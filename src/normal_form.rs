@@ -0,0 +1,128 @@
+use std::collections::HashSet;
+
+use crate::{Expression, Node};
+
+/// Clauses [`Expression::to_cnf`] and [`Expression::to_dnf`] stop at.
+const MAX_CLAUSES: usize = 1024;
+
+/// A normal form would have had more clauses than allowed.
+#[derive(Clone, Debug, PartialEq, Eq, thiserror::Error)]
+#[error("normal form needs more than {max} clauses")]
+pub struct NormalFormError {
+    pub max: usize,
+}
+
+/// Which group is the outer one of the normal form.
+#[derive(Clone, Copy, PartialEq)]
+enum Form {
+    Conjunctive,
+    Disjunctive,
+}
+
+type Clauses = Vec<Vec<Expression>>;
+
+type Join = fn(Vec<Expression>) -> Node;
+
+/// The clauses of `expression`, which must have its negations on comparisons only.
+///
+/// Each clause is a list of comparisons, possibly negated. In disjunctive form `All` groups
+/// are distributed over `AnyOf` groups, in conjunctive form the other way around.
+fn clauses(expression: Expression, form: Form, max: usize) -> Result<Clauses, NormalFormError> {
+    let (outer, inner) = match (expression.node, form) {
+        (Node::AnyOf(expressions), Form::Disjunctive)
+        | (Node::All(expressions), Form::Conjunctive) => (Some(expressions), None),
+        (Node::All(expressions), Form::Disjunctive)
+        | (Node::AnyOf(expressions), Form::Conjunctive) => (None, Some(expressions)),
+        (node, _) => return Ok(vec![vec![node.into()]]),
+    };
+    if let Some(expressions) = outer {
+        let mut result = Vec::new();
+        for expression in expressions {
+            result.append(&mut clauses(expression, form, max)?);
+            if result.len() > max {
+                return Err(NormalFormError { max });
+            }
+        }
+        return Ok(result);
+    }
+    // Every combination of one clause from each operand.
+    let mut result = vec![Vec::new()];
+    for expression in inner.into_iter().flatten() {
+        let operand = clauses(expression, form, max)?;
+        if result.len() * operand.len() > max {
+            return Err(NormalFormError { max });
+        }
+        result = result
+            .iter()
+            .flat_map(|left| {
+                operand
+                    .iter()
+                    .map(move |right| left.iter().chain(right).cloned().collect())
+            })
+            .collect();
+    }
+    Ok(result)
+}
+
+fn normal_form(
+    expression: &Expression,
+    form: Form,
+    max: usize,
+) -> Result<Expression, NormalFormError> {
+    let (outer, inner): (Join, Join) = match form {
+        Form::Conjunctive => (Node::All, Node::AnyOf),
+        Form::Disjunctive => (Node::AnyOf, Node::All),
+    };
+    let mut seen = HashSet::new();
+    let mut groups = Vec::new();
+    for clause in clauses(expression.simplify(), form, max)? {
+        let mut literals = Vec::with_capacity(clause.len());
+        for literal in clause {
+            if !literals.contains(&literal) {
+                literals.push(literal);
+            }
+        }
+        let group = Expression::from(inner(literals));
+        if seen.insert(group.clone()) {
+            groups.push(group);
+        }
+    }
+    Ok(outer(groups).into())
+}
+
+impl Expression {
+    /// Conjunctive normal form of the expression, with at most 1024 clauses.
+    ///
+    /// See [`Expression::to_cnf_with`].
+    pub fn to_cnf(&self) -> Result<Expression, NormalFormError> {
+        self.to_cnf_with(MAX_CLAUSES)
+    }
+
+    /// Conjunctive normal form of the expression: always an `All` of `AnyOf` groups, each of
+    /// them holding comparisons that may be negated. The expression is
+    /// [simplified](Expression::simplify) first.
+    ///
+    /// Distributing groups over each other can make the result exponentially larger, so
+    /// this fails once there would be more than `max_clauses` groups, counting
+    /// intermediate results too.
+    pub fn to_cnf_with(&self, max_clauses: usize) -> Result<Expression, NormalFormError> {
+        normal_form(self, Form::Conjunctive, max_clauses)
+    }
+
+    /// Disjunctive normal form of the expression, with at most 1024 clauses.
+    ///
+    /// See [`Expression::to_dnf_with`].
+    pub fn to_dnf(&self) -> Result<Expression, NormalFormError> {
+        self.to_dnf_with(MAX_CLAUSES)
+    }
+
+    /// Disjunctive normal form of the expression: always an `AnyOf` of `All` groups, each
+    /// of them holding comparisons that may be negated, so every group can run as a query of
+    /// its own. The expression is [simplified](Expression::simplify) first.
+    ///
+    /// Fails once there would be more than `max_clauses` groups, like
+    /// [`Expression::to_cnf_with`].
+    pub fn to_dnf_with(&self, max_clauses: usize) -> Result<Expression, NormalFormError> {
+        normal_form(self, Form::Disjunctive, max_clauses)
+    }
+}
@@ -1,3 +1,42 @@
 pub mod atom;
 pub mod comparison;
+pub mod context;
+pub mod error;
 pub mod relation;
+
+use nom::{character::complete::multispace0, error::ParseError};
+
+use context::{Context, ParseLimits};
+use error::{Error, IResult, Limit};
+use relation::{disjunction, Relation};
+
+/// `Search -> Or`, optionally followed by anything when `lenient` is set.
+pub fn search<'a>(
+    input: &'a str,
+    lenient: bool,
+    limits: &ParseLimits,
+) -> IResult<'a, Box<Relation>> {
+    if input.len() > limits.max_input_length {
+        let mut end = limits.max_input_length;
+        while !input.is_char_boundary(end) {
+            end -= 1;
+        }
+        return Err(nom::Err::Failure(Error::limit(
+            &input[end..],
+            Limit::InputLength,
+            limits.max_input_length,
+        )));
+    }
+    let ctx = Context::new(limits, lenient);
+    let (rest, (relation, pending)) = disjunction(input, &ctx, 1)?;
+    if lenient {
+        return Ok((rest, relation));
+    }
+    let (rest, _) = multispace0(rest)?;
+    if !rest.is_empty() {
+        return Err(nom::Err::Error(
+            pending.or(Error::expected(rest, "end of input")),
+        ));
+    }
+    Ok((rest, relation))
+}
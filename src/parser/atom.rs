@@ -3,13 +3,24 @@ use std::ops::Deref;
 use nom::{
     branch::alt,
     bytes::complete::{escaped, tag},
-    character::complete::{none_of, one_of, space0},
-    combinator::map_res,
-    multi::separated_list0,
-    sequence::{delimited, pair, tuple},
-    IResult,
+    character::complete::{multispace0, none_of, one_of},
+    combinator::map,
+    error::ParseError,
+    sequence::{terminated, tuple},
 };
 
+use super::{
+    context::Context,
+    error::{Error, IResult, Limit},
+};
+
+/// Matches `token` exactly, reporting it as the expected token on failure.
+pub fn symbol<'a>(token: &'static str) -> impl FnMut(&'a str) -> IResult<'a, &'a str> {
+    move |input| {
+        tag(token)(input).map_err(|err: nom::Err<Error>| err.map(|_| Error::expected(input, token)))
+    }
+}
+
 #[derive(Debug)]
 pub struct Text(pub String);
 impl Deref for Text {
@@ -18,14 +29,19 @@ impl Deref for Text {
         self.0.as_str()
     }
 }
-pub fn text(input: &str) -> IResult<&str, Text> {
+pub fn text<'a>(input: &'a str, ctx: &Context) -> IResult<'a, Text> {
     let esc = escaped(none_of("\\\""), '\\', one_of("\\\""));
     let esc_or_empty = alt((esc, tag("")));
-    map_res(delimited(tag("\""), esc_or_empty, tag("\"")), |s: &str| {
-        Result::<Text, nom::Err<nom::error::Error<&str>>>::Ok(Text(
-            s.replace("\\\\", "\\").replace("\\\"", "\"").to_string(),
-        ))
-    })(input)
+    let (rest, _) = tag("\"")(input)
+        .map_err(|err: nom::Err<Error>| err.map(|_| Error::expected(input, "string")))?;
+    let (rest, s) = terminated(esc_or_empty, symbol("\""))(rest)?;
+    ctx.check(
+        input,
+        Limit::TextLength,
+        s.len(),
+        ctx.limits.max_text_length,
+    )?;
+    Ok((rest, Text(s.replace("\\\\", "\\").replace("\\\"", "\""))))
 }
 
 #[derive(Debug)]
@@ -36,28 +52,73 @@ impl Deref for Array {
         self.0.as_slice()
     }
 }
-pub fn array(input: &str) -> IResult<&str, Array> {
-    let left = pair(tag("["), space0);
-    let right = pair(space0, tag("]"));
-    let separator = tuple((space0, tag(","), space0));
-    map_res(
-        delimited(left, separated_list0(separator, text), right),
-        |texts: Vec<Text>| {
-            Result::<Array, nom::Err<nom::error::Error<&str>>>::Ok(Array(
-                texts.into_iter().map(|t| t.0).collect(),
-            ))
+pub fn array<'a>(input: &'a str, ctx: &Context) -> IResult<'a, Array> {
+    let (rest, _) = tuple((symbol("["), multispace0))(input)?;
+    let (mut rest, first) = match text(rest, ctx) {
+        Ok(ok) => ok,
+        Err(nom::Err::Error(err)) => {
+            let (rest, _) = symbol("]")(rest).map_err(|e| e.map(|e| e.or(err)))?;
+            return Ok((rest, Array(Vec::new())));
+        }
+        Err(err) => return Err(err),
+    };
+    let mut texts = vec![first.0];
+    loop {
+        let (before_separator, _) = multispace0(rest)?;
+        if let Ok((rest, _)) = symbol("]")(before_separator) {
+            return Ok((rest, Array(texts)));
+        }
+        let (after_separator, _) = tuple((symbol(","), multispace0))(before_separator)
+            .map_err(|e| e.map(|e| e.or(Error::expected(before_separator, "]"))))?;
+        ctx.check(
+            after_separator,
+            Limit::ArrayLength,
+            texts.len() + 1,
+            ctx.limits.max_array_length,
+        )?;
+        let (after_text, t) = text(after_separator, ctx)?;
+        texts.push(t.0);
+        rest = after_text;
+    }
+}
+
+/// Bounds of a range, each inclusive when its bracket is square.
+#[derive(Debug)]
+pub struct Interval {
+    pub lower: String,
+    pub lower_inclusive: bool,
+    pub upper: String,
+    pub upper_inclusive: bool,
+}
+pub fn interval<'a>(input: &'a str, ctx: &Context) -> IResult<'a, Interval> {
+    let (rest, open) = alt((symbol("["), symbol("(")))(input)?;
+    let (rest, (_, lower, _, _, _, upper, _)) = tuple((
+        multispace0,
+        |i| text(i, ctx),
+        multispace0,
+        symbol(","),
+        multispace0,
+        |i| text(i, ctx),
+        multispace0,
+    ))(rest)?;
+    let (rest, close) = alt((symbol("]"), symbol(")")))(rest)?;
+    Ok((
+        rest,
+        Interval {
+            lower: lower.0,
+            lower_inclusive: open == "[",
+            upper: upper.0,
+            upper_inclusive: close == "]",
         },
-    )(input)
+    ))
 }
 
 macro_rules! operator {
     ($sname:ident, $fname:ident, $symbol:literal) => {
         #[derive(Debug)]
         pub struct $sname;
-        pub fn $fname(input: &str) -> IResult<&str, $sname> {
-            map_res(tag($symbol), |_| {
-                Result::<$sname, nom::Err<nom::error::Error<&str>>>::Ok($sname)
-            })(input)
+        pub fn $fname(input: &str) -> IResult<'_, $sname> {
+            map(symbol($symbol), |_| $sname)(input)
         }
     };
 }
@@ -66,8 +127,16 @@ operator!(Equal, equal, "=");
 operator!(EqualCI, equal_ci, "~");
 operator!(Greater, greater, ">");
 operator!(Less, less, "<");
+operator!(GreaterEqual, greater_equal, ">=");
+operator!(LessEqual, less_equal, "<=");
+operator!(NotEqual, not_equal, "!=");
+operator!(Between, between, "..");
+operator!(Contains, contains, "*=");
+operator!(StartsWith, starts_with, "^=");
+operator!(EndsWith, ends_with, "$=");
 operator!(Wildcard, wildcard, "*");
 operator!(Regex, regex, "$");
+operator!(RegexCI, regex_ci, "$~");
 operator!(Any, any, "?");
 operator!(Null, null, "-");
 operator!(And, and, "&");
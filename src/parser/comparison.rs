@@ -1,6 +1,10 @@
-use nom::{character::complete::space0, combinator::map_res, sequence::tuple, IResult};
+use nom::{character::complete::multispace0, combinator::map, error::ParseError, sequence::tuple};
 
-use super::atom::*;
+use super::{
+    atom::*,
+    context::Context,
+    error::{Error, IResult},
+};
 
 macro_rules! bi_comparison {
     ($sname:ident, $left_type:ty, $oper_type:ty, $right_type:ty, $fname:ident, $left_func:ident, $oper_func:ident, $right_func:ident) => {
@@ -9,11 +13,17 @@ macro_rules! bi_comparison {
             pub left: $left_type,
             pub right: $right_type,
         }
-        pub fn $fname(input: &str) -> IResult<&str, $sname> {
-            map_res(
-                tuple(($left_func, space0, $oper_func, space0, $right_func)),
+        pub fn $fname<'a>(input: &'a str, ctx: &Context) -> IResult<'a, $sname> {
+            map(
+                tuple((
+                    |i| $left_func(i, ctx),
+                    multispace0,
+                    $oper_func,
+                    multispace0,
+                    |i| $right_func(i, ctx),
+                )),
                 |(left, _, _, _, right): ($left_type, &str, $oper_type, &str, $right_type)| {
-                    Result::<$sname, nom::Err<nom::error::Error<&str>>>::Ok($sname { left, right })
+                    $sname { left, right }
                 },
             )(input)
         }
@@ -33,6 +43,36 @@ bi_comparison!(
 );
 bi_comparison!(IsGreater, Text, Greater, Text, is_greater, text, greater, text);
 bi_comparison!(IsLess, Text, Less, Text, is_less, text, less, text);
+bi_comparison!(
+    IsGreaterEqual,
+    Text,
+    GreaterEqual,
+    Text,
+    is_greater_equal,
+    text,
+    greater_equal,
+    text
+);
+bi_comparison!(
+    IsLessEqual,
+    Text,
+    LessEqual,
+    Text,
+    is_less_equal,
+    text,
+    less_equal,
+    text
+);
+bi_comparison!(
+    IsNotEqual,
+    Text,
+    NotEqual,
+    Text,
+    is_not_equal,
+    text,
+    not_equal,
+    text
+);
 bi_comparison!(
     IsWildcard,
     Text,
@@ -43,19 +83,58 @@ bi_comparison!(
     wildcard,
     text
 );
+bi_comparison!(IsBetween, Text, Between, Interval, is_between, text, between, interval);
+bi_comparison!(
+    IsContains,
+    Text,
+    Contains,
+    Text,
+    is_contains,
+    text,
+    contains,
+    text
+);
+bi_comparison!(
+    IsStartsWith,
+    Text,
+    StartsWith,
+    Text,
+    is_starts_with,
+    text,
+    starts_with,
+    text
+);
+bi_comparison!(
+    IsEndsWith,
+    Text,
+    EndsWith,
+    Text,
+    is_ends_with,
+    text,
+    ends_with,
+    text
+);
 bi_comparison!(IsRegex, Text, Regex, Text, is_regex, text, regex, text);
+bi_comparison!(
+    IsRegexCI,
+    Text,
+    RegexCI,
+    Text,
+    is_regex_ci,
+    text,
+    regex_ci,
+    text
+);
 bi_comparison!(IsAny, Text, Any, Array, is_any, text, any, array);
 
 macro_rules! uni_comparison {
     ($sname:ident, $oper_type:ident, $target_type:ident, $fname:ident, $oper_func:ident, $target_func:ident) => {
         #[derive(Debug)]
         pub struct $sname(pub $target_type);
-        pub fn $fname(input: &str) -> IResult<&str, $sname> {
-            map_res(
-                tuple(($target_func, space0, $oper_func)),
-                |(target, _, _): ($target_type, &str, $oper_type)| {
-                    Result::<$sname, nom::Err<nom::error::Error<&str>>>::Ok($sname(target))
-                },
+        pub fn $fname<'a>(input: &'a str, ctx: &Context) -> IResult<'a, $sname> {
+            map(
+                tuple((|i| $target_func(i, ctx), multispace0, $oper_func)),
+                |(target, _, _): ($target_type, &str, $oper_type)| $sname(target),
             )(input)
         }
     };
@@ -71,13 +150,19 @@ macro_rules! comparison {
             $sname($sname),
             )*
         }
-        pub fn comparison(input: &str) -> IResult<&str, Comparison> {
+        pub fn comparison<'a>(input: &'a str, ctx: &Context) -> IResult<'a, Comparison> {
+            let mut error = Error::from_error_kind(input, nom::error::ErrorKind::Alt);
             $(
-            if let Ok((rest, matched)) = $fname(input) {
-                return Ok((rest, Comparison::$sname(matched)));
+            match $fname(input, ctx) {
+                Ok((rest, matched)) => {
+                    ctx.add_node(input)?;
+                    return Ok((rest, Comparison::$sname(matched)));
+                }
+                Err(nom::Err::Error(err)) => error = error.or(err),
+                Err(err) => return Err(err),
             }
             )*
-            Err(nom::Err::Error(nom::error::Error::new(input, nom::error::ErrorKind::Fail)))
+            Err(nom::Err::Error(error))
         }
     };
 }
@@ -87,8 +172,16 @@ comparison!(
     (IsEqualCI, is_equal_ci),
     (IsGreater, is_greater),
     (IsLess, is_less),
+    (IsGreaterEqual, is_greater_equal),
+    (IsLessEqual, is_less_equal),
+    (IsNotEqual, is_not_equal),
+    (IsBetween, is_between),
+    (IsContains, is_contains),
+    (IsStartsWith, is_starts_with),
+    (IsEndsWith, is_ends_with),
     (IsWildcard, is_wildcard),
     (IsRegex, is_regex),
+    (IsRegexCI, is_regex_ci),
     (IsAny, is_any),
     (IsNull, is_null),
 );
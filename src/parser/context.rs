@@ -0,0 +1,70 @@
+use std::cell::Cell;
+
+use super::error::{Error, Limit};
+
+/// Bounds enforced while parsing, so that untrusted input can neither exhaust memory nor
+/// nest deep enough to overflow the stack of the parser or of an interpreter.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseLimits {
+    /// Deepest nesting of groups and negations.
+    pub max_depth: usize,
+    /// Longest input, in bytes.
+    pub max_input_length: usize,
+    /// Most comparisons and logical operators in the whole expression.
+    pub max_nodes: usize,
+    /// Most strings in a single array.
+    pub max_array_length: usize,
+    /// Longest string, in bytes as written between the quotes.
+    pub max_text_length: usize,
+}
+
+impl Default for ParseLimits {
+    fn default() -> Self {
+        Self {
+            max_depth: 64,
+            max_input_length: 64 * 1024,
+            max_nodes: 4096,
+            max_array_length: 1024,
+            max_text_length: 4096,
+        }
+    }
+}
+
+/// State shared by every combinator during one parse.
+pub struct Context<'l> {
+    pub limits: &'l ParseLimits,
+    /// Whether parsing may stop before an operator whose right operand does not parse,
+    /// leaving the operator as trailing input.
+    pub lenient: bool,
+    nodes: Cell<usize>,
+}
+
+impl<'l> Context<'l> {
+    pub fn new(limits: &'l ParseLimits, lenient: bool) -> Self {
+        Self {
+            limits,
+            lenient,
+            nodes: Cell::new(0),
+        }
+    }
+
+    /// Fails unless `value` is within `max`, blaming the input at `input`.
+    pub fn check<'a>(
+        &self,
+        input: &'a str,
+        limit: Limit,
+        value: usize,
+        max: usize,
+    ) -> Result<(), nom::Err<Error<'a>>> {
+        if value > max {
+            return Err(nom::Err::Failure(Error::limit(input, limit, max)));
+        }
+        Ok(())
+    }
+
+    /// Counts one more node of the expression starting at `input`.
+    pub fn add_node<'a>(&self, input: &'a str) -> Result<(), nom::Err<Error<'a>>> {
+        self.nodes.set(self.nodes.get() + 1);
+        self.check(input, Limit::Nodes, self.nodes.get(), self.limits.max_nodes)
+    }
+}
@@ -0,0 +1,182 @@
+use std::{cmp::Ordering, fmt};
+
+use nom::error::ErrorKind;
+
+/// Error threaded through the combinators.
+///
+/// It remembers the furthest position any alternative reached and every token that would
+/// have been accepted there, so `alt` can report all of them at once.
+#[derive(Debug)]
+pub struct Error<'a> {
+    pub input: &'a str,
+    pub expected: Vec<&'static str>,
+    pub limit: Option<(Limit, usize)>,
+}
+
+impl<'a> Error<'a> {
+    pub fn expected(input: &'a str, token: &'static str) -> Self {
+        Self {
+            input,
+            expected: vec![token],
+            limit: None,
+        }
+    }
+
+    pub fn limit(input: &'a str, limit: Limit, max: usize) -> Self {
+        Self {
+            input,
+            expected: Vec::new(),
+            limit: Some((limit, max)),
+        }
+    }
+}
+
+impl<'a> nom::error::ParseError<&'a str> for Error<'a> {
+    fn from_error_kind(input: &'a str, _kind: ErrorKind) -> Self {
+        Self {
+            input,
+            expected: Vec::new(),
+            limit: None,
+        }
+    }
+
+    fn append(_input: &'a str, _kind: ErrorKind, other: Self) -> Self {
+        other
+    }
+
+    fn or(mut self, other: Self) -> Self {
+        // A broken limit ends parsing, whatever else might have matched.
+        if self.limit.is_some() {
+            return self;
+        }
+        if other.limit.is_some() {
+            return other;
+        }
+        // Less remaining input means the alternative got further.
+        match self.input.len().cmp(&other.input.len()) {
+            Ordering::Less => self,
+            Ordering::Greater => other,
+            Ordering::Equal => {
+                for token in other.expected {
+                    if !self.expected.contains(&token) {
+                        self.expected.push(token);
+                    }
+                }
+                self
+            }
+        }
+    }
+}
+
+pub type IResult<'a, O> = nom::IResult<&'a str, O, Error<'a>>;
+
+/// Longest snippet of the offending input kept in a [`ParseError`].
+const SNIPPET_LEN: usize = 16;
+
+/// One of the bounds in [`ParseLimits`](crate::ParseLimits).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Limit {
+    Depth,
+    InputLength,
+    Nodes,
+    ArrayLength,
+    TextLength,
+}
+
+impl fmt::Display for Limit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Limit::Depth => "nesting depth",
+            Limit::InputLength => "input length",
+            Limit::Nodes => "number of nodes",
+            Limit::ArrayLength => "array length",
+            Limit::TextLength => "string length",
+        })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    /// None of these tokens, either a symbol such as `)` or a description such as `string`,
+    /// was found.
+    Expected(Vec<&'static str>),
+    /// The input goes beyond one of the [`ParseLimits`](crate::ParseLimits).
+    LimitExceeded { limit: Limit, max: usize },
+}
+
+/// A GSS string could not be parsed.
+///
+/// `offset` is a byte offset into the input, `line` and `column` are 1-based with the column
+/// counted in characters.
+#[derive(Clone, Debug, PartialEq, Eq, thiserror::Error)]
+pub struct ParseError {
+    pub kind: ParseErrorKind,
+    pub offset: usize,
+    pub line: usize,
+    pub column: usize,
+    pub snippet: String,
+}
+
+impl ParseError {
+    pub(crate) fn new(source: &str, error: Error) -> Self {
+        let offset = source.len() - error.input.len();
+        let consumed = &source[..offset];
+        let line = consumed.matches('\n').count() + 1;
+        let line_start = consumed.rfind('\n').map_or(0, |i| i + 1);
+        let column = consumed[line_start..].chars().count() + 1;
+        let snippet = error
+            .input
+            .chars()
+            .take_while(|c| *c != '\n')
+            .take(SNIPPET_LEN)
+            .collect();
+        let kind = match error.limit {
+            Some((limit, max)) => ParseErrorKind::LimitExceeded { limit, max },
+            None => ParseErrorKind::Expected(error.expected),
+        };
+        Self {
+            kind,
+            offset,
+            line,
+            column,
+            snippet,
+        }
+    }
+}
+
+fn describe(token: &str) -> String {
+    if token.chars().any(|c| c.is_ascii_alphabetic()) {
+        token.to_string()
+    } else {
+        format!("`{}`", token)
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            ParseErrorKind::Expected(expected) => match expected.split_last() {
+                None => write!(f, "unexpected input")?,
+                Some((last, [])) => write!(f, "expected {}", describe(last))?,
+                Some((last, rest)) => write!(
+                    f,
+                    "expected {} or {}",
+                    rest.iter()
+                        .map(|t| describe(t))
+                        .collect::<Vec<_>>()
+                        .join(", "),
+                    describe(last)
+                )?,
+            },
+            ParseErrorKind::LimitExceeded { limit, max } => {
+                write!(f, "{} exceeds the limit of {}", limit, max)?
+            }
+        }
+        write!(f, " at line {}, column {}", self.line, self.column)?;
+        if self.snippet.is_empty() {
+            write!(f, ", found end of input")
+        } else {
+            write!(f, ", found `{}`", self.snippet)
+        }
+    }
+}
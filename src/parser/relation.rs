@@ -1,201 +1,141 @@
-use nom::{
-    branch::alt, bytes::complete::tag, character::complete::space0, combinator::map_res,
-    sequence::tuple, IResult,
-};
+use nom::{character::complete::multispace0, error::ParseError, sequence::tuple};
 
-use super::{atom::*, comparison::*};
+use super::{
+    atom::*,
+    comparison::*,
+    context::Context,
+    error::{Error, IResult, Limit},
+};
 
 #[derive(Debug)]
 pub enum Relation {
     C(Comparison),
-    RAR {
-        left: Box<Relation>,
-        right: Box<Relation>,
-    },
-    RAC {
-        left: Box<Relation>,
-        right: Comparison,
-    },
-    CAR {
-        left: Comparison,
-        right: Box<Relation>,
-    },
-    CAC {
-        left: Comparison,
-        right: Comparison,
-    },
-    ROR {
-        left: Box<Relation>,
-        right: Box<Relation>,
-    },
-    ROC {
-        left: Box<Relation>,
-        right: Comparison,
-    },
-    COR {
-        left: Comparison,
-        right: Box<Relation>,
-    },
-    COC {
-        left: Comparison,
-        right: Comparison,
-    },
-    NR(Box<Relation>),
-    NC(Comparison),
+    And(Vec<Relation>),
+    Or(Vec<Relation>),
+    Not(Box<Relation>),
 }
 
-fn group_start(input: &str) -> IResult<&str, &str> {
-    tag("(")(input)
+fn group_start(input: &str) -> IResult<'_, &str> {
+    symbol("(")(input)
 }
 
-fn group_end(input: &str) -> IResult<&str, &str> {
-    tag(")")(input)
+fn group_end(input: &str) -> IResult<'_, &str> {
+    symbol(")")(input)
 }
 
-fn c(input: &str) -> IResult<&str, Box<Relation>> {
-    map_res(
-        tuple((group_start, space0, comparison, space0, group_end)),
-        |(_, _, c, _, _): (&str, &str, Comparison, &str, &str)| {
-            Result::<Box<Relation>, nom::Err<nom::error::Error<&str>>>::Ok(Box::new(Relation::C(c)))
-        },
-    )(input)
-}
+/// A relation that stopped parsing where it could have gone on, together with the error
+/// telling which tokens would have let it go on. The caller merges that error into its own
+/// if it fails at the same position, so the report lists every acceptable token.
+type Partial<'a> = IResult<'a, (Box<Relation>, Error<'a>)>;
 
-macro_rules! bi_relation {
-    ($fname:ident, $left_func:ident, $oper_func:ident, $right_func:ident, $left_type:ty, $oper_type:ident, $right_type:ty, $relation:ident) => {
-        fn $fname(input: &str) -> IResult<&str, Box<Relation>> {
-            map_res(
-                tuple((
-                    group_start,
-                    space0,
-                    $left_func,
-                    space0,
-                    $oper_func,
-                    space0,
-                    $right_func,
-                    space0,
-                    group_end,
-                )),
-                |(_, _, left, _, _, _, right, _, _): (
-                    &str,
-                    &str,
-                    $left_type,
-                    &str,
-                    $oper_type,
-                    &str,
-                    $right_type,
-                    &str,
-                    &str,
-                )| {
-                    Result::<Box<Relation>, nom::Err<nom::error::Error<&str>>>::Ok(Box::new(
-                        Relation::$relation { left, right },
-                    ))
-                },
-            )(input)
+/// `Primary -> GroupStart Or GroupEnd | Comparison`
+fn primary<'a>(input: &'a str, ctx: &Context, depth: usize) -> IResult<'a, Box<Relation>> {
+    let group = match group_start(input) {
+        Ok((rest, _)) => rest,
+        Err(nom::Err::Error(err)) => {
+            return match comparison(input, ctx) {
+                Ok((rest, c)) => Ok((rest, Box::new(Relation::C(c)))),
+                Err(nom::Err::Error(e)) => Err(nom::Err::Error(err.or(e))),
+                Err(e) => Err(e),
+            };
         }
+        Err(err) => return Err(err),
     };
+    ctx.check(input, Limit::Depth, depth, ctx.limits.max_depth)?;
+    let (rest, _) = multispace0(group)?;
+    let (rest, (relation, pending)) = disjunction(rest, ctx, depth + 1)?;
+    let (rest, _) =
+        tuple((multispace0, group_end))(rest).map_err(|err| err.map(|err| pending.or(err)))?;
+    Ok((rest, relation))
 }
 
-bi_relation!(
-    rar,
-    relation,
-    and,
-    relation,
-    Box<Relation>,
-    And,
-    Box<Relation>,
-    RAR
-);
-bi_relation!(
-    rac,
-    relation,
-    and,
-    comparison,
-    Box<Relation>,
-    And,
-    Comparison,
-    RAC
-);
-bi_relation!(
-    car,
-    comparison,
-    and,
-    relation,
-    Comparison,
-    And,
-    Box<Relation>,
-    CAR
-);
-bi_relation!(cac, comparison, and, comparison, Comparison, And, Comparison, CAC);
-bi_relation!(
-    ror,
-    relation,
-    or,
-    relation,
-    Box<Relation>,
-    Or,
-    Box<Relation>,
-    ROR
-);
-bi_relation!(
-    roc,
-    relation,
-    or,
-    comparison,
-    Box<Relation>,
-    Or,
-    Comparison,
-    ROC
-);
-bi_relation!(
-    cor,
-    comparison,
-    or,
-    relation,
-    Comparison,
-    Or,
-    Box<Relation>,
-    COR
-);
-bi_relation!(coc, comparison, or, comparison, Comparison, Or, Comparison, COC);
-
-macro_rules! uni_relation {
-    ($fname:ident, $oper_func:ident, $target_func:ident, $oper_type:ty, $target_type:ty, $relation:ident) => {
-        fn $fname(input: &str) -> IResult<&str, Box<Relation>> {
-            map_res(
-                tuple((
-                    group_start,
-                    space0,
-                    $oper_func,
-                    space0,
-                    $target_func,
-                    space0,
-                    group_end,
-                )),
-                |(_, _, _, _, target, _, _): (
-                    &str,
-                    &str,
-                    $oper_type,
-                    &str,
-                    $target_type,
-                    &str,
-                    &str,
-                )| {
-                    Result::<Box<Relation>, nom::Err<nom::error::Error<&str>>>::Ok(Box::new(
-                        Relation::$relation(target),
-                    ))
-                },
-            )(input)
+/// `Unary -> Not Unary | Primary`
+fn unary<'a>(input: &'a str, ctx: &Context, depth: usize) -> IResult<'a, Box<Relation>> {
+    match not(input) {
+        Ok((rest, _)) => {
+            ctx.check(input, Limit::Depth, depth, ctx.limits.max_depth)?;
+            let (rest, _) = multispace0(rest)?;
+            let (rest, target) = unary(rest, ctx, depth + 1)?;
+            ctx.add_node(input)?;
+            Ok((rest, Box::new(Relation::Not(target))))
         }
-    };
+        Err(nom::Err::Error(err)) => primary(input, ctx, depth).map_err(|e| e.map(|e| err.or(e))),
+        Err(err) => Err(err),
+    }
 }
 
-uni_relation!(nr, not, relation, Not, Box<Relation>, NR);
-uni_relation!(nc, not, comparison, Not, Comparison, NC);
+/// The operands of a chain, as a `join` node if there is more than one of them.
+fn joined<'a>(
+    input: &'a str,
+    ctx: &Context,
+    mut operands: Vec<Relation>,
+    join: fn(Vec<Relation>) -> Relation,
+) -> Result<Box<Relation>, nom::Err<Error<'a>>> {
+    if operands.len() == 1 {
+        return Ok(Box::new(operands.remove(0)));
+    }
+    ctx.add_node(input)?;
+    Ok(Box::new(join(operands)))
+}
+
+/// Operands separated by `operator`, collected into a single `join` node when there is
+/// more than one of them.
+fn chain<'a, O>(
+    input: &'a str,
+    ctx: &Context,
+    depth: usize,
+    mut operator: impl FnMut(&'a str) -> IResult<'a, O>,
+    operand: impl Fn(&'a str, &Context, usize) -> Partial<'a>,
+    join: fn(Vec<Relation>) -> Relation,
+) -> Partial<'a> {
+    let (mut rest, (first, mut pending)) = operand(input, ctx, depth)?;
+    let mut operands = vec![*first];
+    loop {
+        let after_operator = match tuple((multispace0, &mut operator, multispace0))(rest) {
+            Ok((after_operator, _)) => after_operator,
+            Err(nom::Err::Error(err)) => {
+                let relation = joined(input, ctx, operands, join)?;
+                return Ok((rest, (relation, pending.or(err))));
+            }
+            Err(err) => return Err(err),
+        };
+        let (after_operand, (right, operand_pending)) = match operand(after_operator, ctx, depth) {
+            Ok(parsed) => parsed,
+            // What the old parser did: stop after the last complete operand.
+            Err(nom::Err::Error(err)) if ctx.lenient => {
+                let relation = joined(input, ctx, operands, join)?;
+                return Ok((rest, (relation, pending.or(err))));
+            }
+            Err(err) => return Err(err),
+        };
+        operands.push(*right);
+        pending = operand_pending;
+        rest = after_operand;
+    }
+}
+
+/// `And -> Unary (AndOp Unary)*`
+fn conjunction<'a>(input: &'a str, ctx: &Context, depth: usize) -> Partial<'a> {
+    chain(
+        input,
+        ctx,
+        depth,
+        and,
+        |input, ctx, depth| {
+            let (rest, relation) = unary(input, ctx, depth)?;
+            Ok((
+                rest,
+                (
+                    relation,
+                    Error::from_error_kind(rest, nom::error::ErrorKind::Alt),
+                ),
+            ))
+        },
+        Relation::And,
+    )
+}
 
-pub fn relation(input: &str) -> IResult<&str, Box<Relation>> {
-    map_res(
-        alt((c, rar, rac, car, cac, ror, roc, cor, coc, nr, nc)),
-        |r: Box<Relation>| Result::<Box<Relation>, nom::Err<nom::error::Error<&str>>>::Ok(r),
-    )(input)
+/// `Or -> And (OrOp And)*`
+pub fn disjunction<'a>(input: &'a str, ctx: &Context, depth: usize) -> Partial<'a> {
+    chain(input, ctx, depth, or, conjunction, Relation::Or)
 }
@@ -0,0 +1,320 @@
+//! Serde support for the AST, enabled by the `serde` feature.
+//!
+//! An [`Expression`] is stored as its [`Node`], tagged by `op`:
+//!
+//! ```text
+//! {"op":"all","args":[<expression>, ...]}
+//! {"op":"any_of","args":[<expression>, ...]}
+//! {"op":"not","arg":<expression>}
+//! {"op":"equal","key":"k","value":"v"}        also equal_ci, greater, less, greater_equal,
+//!                                           less_equal, not_equal, contains, starts_with,
+//!                                           ends_with, wildcard, regex, regex_ci
+//! {"op":"between","key":"k","lower":"a","lower_inclusive":true,"upper":"b","upper_inclusive":false}
+//! {"op":"any","key":"k","values":["v", ...]}
+//! {"op":"null","key":"k"}
+//! ```
+//!
+//! Fields that should hold GSS instead can use [`gss`] through `#[serde(with = "...")]`.
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{Bounds, Expression, Node};
+
+#[derive(Serialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+enum NodeRef<'a> {
+    All {
+        args: &'a [Expression],
+    },
+    AnyOf {
+        args: &'a [Expression],
+    },
+    Not {
+        arg: &'a Expression,
+    },
+    Equal {
+        key: &'a str,
+        value: &'a str,
+    },
+    EqualCi {
+        key: &'a str,
+        value: &'a str,
+    },
+    Greater {
+        key: &'a str,
+        value: &'a str,
+    },
+    Less {
+        key: &'a str,
+        value: &'a str,
+    },
+    GreaterEqual {
+        key: &'a str,
+        value: &'a str,
+    },
+    LessEqual {
+        key: &'a str,
+        value: &'a str,
+    },
+    NotEqual {
+        key: &'a str,
+        value: &'a str,
+    },
+    Between {
+        key: &'a str,
+        lower: &'a str,
+        lower_inclusive: bool,
+        upper: &'a str,
+        upper_inclusive: bool,
+    },
+    Contains {
+        key: &'a str,
+        value: &'a str,
+    },
+    StartsWith {
+        key: &'a str,
+        value: &'a str,
+    },
+    EndsWith {
+        key: &'a str,
+        value: &'a str,
+    },
+    Wildcard {
+        key: &'a str,
+        value: &'a str,
+    },
+    Regex {
+        key: &'a str,
+        value: &'a str,
+    },
+    RegexCi {
+        key: &'a str,
+        value: &'a str,
+    },
+    Any {
+        key: &'a str,
+        values: &'a [String],
+    },
+    Null {
+        key: &'a str,
+    },
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+enum NodeDef {
+    All {
+        args: Vec<Expression>,
+    },
+    AnyOf {
+        args: Vec<Expression>,
+    },
+    Not {
+        arg: Box<Expression>,
+    },
+    Equal {
+        key: String,
+        value: String,
+    },
+    EqualCi {
+        key: String,
+        value: String,
+    },
+    Greater {
+        key: String,
+        value: String,
+    },
+    Less {
+        key: String,
+        value: String,
+    },
+    GreaterEqual {
+        key: String,
+        value: String,
+    },
+    LessEqual {
+        key: String,
+        value: String,
+    },
+    NotEqual {
+        key: String,
+        value: String,
+    },
+    Between {
+        key: String,
+        lower: String,
+        lower_inclusive: bool,
+        upper: String,
+        upper_inclusive: bool,
+    },
+    Contains {
+        key: String,
+        value: String,
+    },
+    StartsWith {
+        key: String,
+        value: String,
+    },
+    EndsWith {
+        key: String,
+        value: String,
+    },
+    Wildcard {
+        key: String,
+        value: String,
+    },
+    Regex {
+        key: String,
+        value: String,
+    },
+    RegexCi {
+        key: String,
+        value: String,
+    },
+    Any {
+        key: String,
+        values: Vec<String>,
+    },
+    Null {
+        key: String,
+    },
+}
+
+impl Serialize for Node {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Node::All(args) => NodeRef::All { args },
+            Node::AnyOf(args) => NodeRef::AnyOf { args },
+            Node::Not(arg) => NodeRef::Not { arg },
+            Node::Equal(key, value) => NodeRef::Equal { key, value },
+            Node::EqualCI(key, value) => NodeRef::EqualCi { key, value },
+            Node::Greater(key, value) => NodeRef::Greater { key, value },
+            Node::Less(key, value) => NodeRef::Less { key, value },
+            Node::GreaterEqual(key, value) => NodeRef::GreaterEqual { key, value },
+            Node::LessEqual(key, value) => NodeRef::LessEqual { key, value },
+            Node::NotEqual(key, value) => NodeRef::NotEqual { key, value },
+            Node::Between(key, bounds) => NodeRef::Between {
+                key,
+                lower: &bounds.lower,
+                lower_inclusive: bounds.lower_inclusive,
+                upper: &bounds.upper,
+                upper_inclusive: bounds.upper_inclusive,
+            },
+            Node::Contains(key, value) => NodeRef::Contains { key, value },
+            Node::StartsWith(key, value) => NodeRef::StartsWith { key, value },
+            Node::EndsWith(key, value) => NodeRef::EndsWith { key, value },
+            Node::Wildcard(key, value) => NodeRef::Wildcard { key, value },
+            Node::Regex(key, value) => NodeRef::Regex { key, value },
+            Node::RegexCI(key, value) => NodeRef::RegexCi { key, value },
+            Node::Any(key, values) => NodeRef::Any { key, values },
+            Node::Null(key) => NodeRef::Null { key },
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Node {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(match NodeDef::deserialize(deserializer)? {
+            NodeDef::All { args } => Node::All(args),
+            NodeDef::AnyOf { args } => Node::AnyOf(args),
+            NodeDef::Not { arg } => Node::Not(arg),
+            NodeDef::Equal { key, value } => Node::Equal(key, value),
+            NodeDef::EqualCi { key, value } => Node::EqualCI(key, value),
+            NodeDef::Greater { key, value } => Node::Greater(key, value),
+            NodeDef::Less { key, value } => Node::Less(key, value),
+            NodeDef::GreaterEqual { key, value } => Node::GreaterEqual(key, value),
+            NodeDef::LessEqual { key, value } => Node::LessEqual(key, value),
+            NodeDef::NotEqual { key, value } => Node::NotEqual(key, value),
+            NodeDef::Between {
+                key,
+                lower,
+                lower_inclusive,
+                upper,
+                upper_inclusive,
+            } => Node::Between(
+                key,
+                Bounds {
+                    lower,
+                    lower_inclusive,
+                    upper,
+                    upper_inclusive,
+                },
+            ),
+            NodeDef::Contains { key, value } => Node::Contains(key, value),
+            NodeDef::StartsWith { key, value } => Node::StartsWith(key, value),
+            NodeDef::EndsWith { key, value } => Node::EndsWith(key, value),
+            NodeDef::Wildcard { key, value } => Node::Wildcard(key, value),
+            NodeDef::Regex { key, value } => Node::Regex(key, value),
+            NodeDef::RegexCi { key, value } => Node::RegexCI(key, value),
+            NodeDef::Any { key, values } => Node::Any(key, values),
+            NodeDef::Null { key } => Node::Null(key),
+        })
+    }
+}
+
+impl Serialize for Expression {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.node.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Expression {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(Self {
+            node: Node::deserialize(deserializer)?,
+        })
+    }
+}
+
+/// (De)serializes an [`Expression`] as a GSS string, for use with
+/// `#[serde(with = "flp_gsp::serialization::gss")]`.
+///
+/// The string is the [`Display`](std::fmt::Display) form, so an expression the parser could
+/// not have produced comes back normalized: a group of one expression as that expression,
+/// and a group directly in a group of the same kind merged into it. Either way it matches the
+/// same values. An expression holding an empty `All` or `AnyOf` has no GSS form at all and
+/// fails to serialize.
+pub mod gss {
+    use serde::{de, ser, Deserialize, Deserializer, Serializer};
+
+    use crate::{
+        visit::{walk_expressions, Visitor},
+        Expression,
+    };
+
+    /// Finds the empty groups, i.e. the constants, that GSS cannot spell.
+    #[derive(Default)]
+    struct Constant(bool);
+
+    impl Visitor for Constant {
+        fn visit_all(&mut self, expressions: &[Expression]) {
+            self.0 |= expressions.is_empty();
+            walk_expressions(self, expressions)
+        }
+
+        fn visit_any_of(&mut self, expressions: &[Expression]) {
+            self.0 |= expressions.is_empty();
+            walk_expressions(self, expressions)
+        }
+    }
+
+    pub fn serialize<S: Serializer>(
+        expression: &Expression,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let mut constant = Constant::default();
+        constant.visit_expression(expression);
+        if constant.0 {
+            return Err(ser::Error::custom(
+                "an expression with an empty group has no GSS form",
+            ));
+        }
+        serializer.collect_str(expression)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Expression, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        Expression::try_from_str(&s).map_err(de::Error::custom)
+    }
+}
@@ -0,0 +1,140 @@
+use crate::{
+    visit::{fold_expressions, Fold},
+    Expression, Node,
+};
+
+fn always() -> Expression {
+    Node::All(Vec::new()).into()
+}
+
+fn never() -> Expression {
+    Node::AnyOf(Vec::new()).into()
+}
+
+/// `!expression` with the negation pushed down to the comparisons.
+fn negate(expression: Expression) -> Expression {
+    match expression.node {
+        Node::Not(expression) => *expression,
+        Node::All(expressions) => Node::AnyOf(expressions.into_iter().map(negate).collect()).into(),
+        Node::AnyOf(expressions) => Node::All(expressions.into_iter().map(negate).collect()).into(),
+        node => Node::Not(Box::new(node.into())).into(),
+    }
+}
+
+/// Drops repeated operands, keeping the first of each so the order the user wrote is kept.
+fn dedup<T: PartialEq>(items: Vec<T>) -> Vec<T> {
+    let mut unique = Vec::with_capacity(items.len());
+    for item in items {
+        if !unique.contains(&item) {
+            unique.push(item);
+        }
+    }
+    unique
+}
+
+/// A group of what is left of the operands, or the only one of them.
+fn junction(mut expressions: Vec<Expression>, join: fn(Vec<Expression>) -> Node) -> Expression {
+    if expressions.len() == 1 {
+        return expressions.remove(0);
+    }
+    join(expressions).into()
+}
+
+/// `key` checked against every target, as `=` when there is only one of them.
+fn equality(key: String, mut targets: Vec<String>) -> Expression {
+    match targets.len() {
+        1 => Node::Equal(key, targets.remove(0)).into(),
+        _ => Node::Any(key, targets).into(),
+    }
+}
+
+/// Merges equality checks of one key into a single `?`, in place of the first of them.
+fn merge_equalities(expressions: Vec<Expression>) -> Vec<Expression> {
+    let mut merged: Vec<Expression> = Vec::with_capacity(expressions.len());
+    for expression in expressions {
+        let (key, targets) = match expression.node {
+            Node::Equal(key, target) => (key, vec![target]),
+            Node::Any(key, targets) => (key, targets),
+            node => {
+                merged.push(node.into());
+                continue;
+            }
+        };
+        let existing = merged.iter().position(
+            |merged| matches!(&merged.node, Node::Equal(k, _) | Node::Any(k, _) if *k == key),
+        );
+        match existing {
+            Some(i) => {
+                let previous = match std::mem::replace(&mut merged[i].node, Node::All(Vec::new())) {
+                    Node::Equal(_, target) => vec![target],
+                    Node::Any(_, targets) => targets,
+                    _ => unreachable!("only equalities are merged"),
+                };
+                merged[i] = equality(key, dedup(previous.into_iter().chain(targets).collect()));
+            }
+            None => merged.push(equality(key, targets)),
+        }
+    }
+    merged
+}
+
+struct Simplify;
+
+impl Fold for Simplify {
+    fn fold_all(&mut self, expressions: Vec<Expression>) -> Expression {
+        let mut operands = Vec::with_capacity(expressions.len());
+        for expression in fold_expressions(self, expressions) {
+            match expression.node {
+                Node::All(nested) => operands.extend(nested),
+                Node::AnyOf(nested) if nested.is_empty() => return never(),
+                node => operands.push(node.into()),
+            }
+        }
+        junction(dedup(operands), Node::All)
+    }
+
+    fn fold_any_of(&mut self, expressions: Vec<Expression>) -> Expression {
+        let mut operands = Vec::with_capacity(expressions.len());
+        for expression in fold_expressions(self, expressions) {
+            match expression.node {
+                Node::AnyOf(nested) => operands.extend(nested),
+                Node::All(nested) if nested.is_empty() => return always(),
+                node => operands.push(node.into()),
+            }
+        }
+        junction(dedup(merge_equalities(operands)), Node::AnyOf)
+    }
+
+    fn fold_not(&mut self, expression: Expression) -> Expression {
+        match self.fold_expression(expression).node {
+            // De Morgan, after which the groups may flatten into their parent.
+            node @ (Node::All(_) | Node::AnyOf(_)) => self.fold_expression(negate(node.into())),
+            node => negate(node.into()),
+        }
+    }
+
+    fn fold_any(&mut self, key: String, targets: Vec<String>) -> Expression {
+        if targets.is_empty() {
+            return never();
+        }
+        Node::Any(key, dedup(targets)).into()
+    }
+}
+
+impl Expression {
+    /// An equivalent expression that is usually smaller, so it makes for shorter SQL or
+    /// Hasura filters.
+    ///
+    /// Double negations are removed and the remaining ones are pushed down to comparisons
+    /// by De Morgan's laws, groups of the same kind are flattened, repeated operands are
+    /// dropped, and `=`/`?` checks of the same key in a `|` group are merged into one `?`.
+    /// `?` with an empty array is folded into the constant false, written as an empty
+    /// `AnyOf`, which is then folded into the groups around it. An expression that is
+    /// constant as a whole simplifies to an empty `All` (true) or `AnyOf` (false).
+    ///
+    /// Merging assumes `=` and `?` agree on what equal means, which holds for every SQL
+    /// and Hasura backend and for the default `EvaluateRule` of the evaluator.
+    pub fn simplify(&self) -> Expression {
+        Simplify.fold_expression(self.clone())
+    }
+}
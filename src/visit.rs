@@ -0,0 +1,213 @@
+//! Traversal of the AST without writing out the recursion over [`Node`] by hand.
+//!
+//! Every method of [`Visitor`] and [`Fold`] defaults to recursing into the children, or to
+//! doing nothing for comparisons, so an implementation only overrides the nodes it cares
+//! about. An overriding method can call the matching `walk_*` function, or the default
+//! behaviour of [`Fold`] by rebuilding the node, to keep recursing.
+
+use crate::{Bounds, Expression, Node};
+
+/// Read-only traversal of an expression.
+pub trait Visitor {
+    fn visit_expression(&mut self, expression: &Expression) {
+        walk_expression(self, expression)
+    }
+
+    fn visit_all(&mut self, expressions: &[Expression]) {
+        walk_expressions(self, expressions)
+    }
+
+    fn visit_any_of(&mut self, expressions: &[Expression]) {
+        walk_expressions(self, expressions)
+    }
+
+    fn visit_not(&mut self, expression: &Expression) {
+        self.visit_expression(expression)
+    }
+
+    fn visit_equal(&mut self, _key: &str, _target: &str) {}
+
+    fn visit_equal_ci(&mut self, _key: &str, _target: &str) {}
+
+    fn visit_greater(&mut self, _key: &str, _target: &str) {}
+
+    fn visit_less(&mut self, _key: &str, _target: &str) {}
+
+    fn visit_greater_equal(&mut self, _key: &str, _target: &str) {}
+
+    fn visit_less_equal(&mut self, _key: &str, _target: &str) {}
+
+    fn visit_not_equal(&mut self, _key: &str, _target: &str) {}
+
+    fn visit_between(&mut self, _key: &str, _bounds: &Bounds) {}
+
+    fn visit_contains(&mut self, _key: &str, _target: &str) {}
+
+    fn visit_starts_with(&mut self, _key: &str, _target: &str) {}
+
+    fn visit_ends_with(&mut self, _key: &str, _target: &str) {}
+
+    fn visit_wildcard(&mut self, _key: &str, _target: &str) {}
+
+    fn visit_regex(&mut self, _key: &str, _target: &str) {}
+
+    fn visit_regex_ci(&mut self, _key: &str, _target: &str) {}
+
+    fn visit_any(&mut self, _key: &str, _targets: &[String]) {}
+
+    fn visit_null(&mut self, _key: &str) {}
+}
+
+/// Dispatches `expression` to the method of `visitor` for its node.
+pub fn walk_expression<V: Visitor + ?Sized>(visitor: &mut V, expression: &Expression) {
+    match &expression.node {
+        Node::All(expressions) => visitor.visit_all(expressions),
+        Node::AnyOf(expressions) => visitor.visit_any_of(expressions),
+        Node::Not(expression) => visitor.visit_not(expression),
+        Node::Equal(key, target) => visitor.visit_equal(key, target),
+        Node::EqualCI(key, target) => visitor.visit_equal_ci(key, target),
+        Node::Greater(key, target) => visitor.visit_greater(key, target),
+        Node::Less(key, target) => visitor.visit_less(key, target),
+        Node::GreaterEqual(key, target) => visitor.visit_greater_equal(key, target),
+        Node::LessEqual(key, target) => visitor.visit_less_equal(key, target),
+        Node::NotEqual(key, target) => visitor.visit_not_equal(key, target),
+        Node::Between(key, bounds) => visitor.visit_between(key, bounds),
+        Node::Contains(key, target) => visitor.visit_contains(key, target),
+        Node::StartsWith(key, target) => visitor.visit_starts_with(key, target),
+        Node::EndsWith(key, target) => visitor.visit_ends_with(key, target),
+        Node::Wildcard(key, target) => visitor.visit_wildcard(key, target),
+        Node::Regex(key, target) => visitor.visit_regex(key, target),
+        Node::RegexCI(key, target) => visitor.visit_regex_ci(key, target),
+        Node::Any(key, targets) => visitor.visit_any(key, targets),
+        Node::Null(key) => visitor.visit_null(key),
+    }
+}
+
+/// Visits every expression in order.
+pub fn walk_expressions<V: Visitor + ?Sized>(visitor: &mut V, expressions: &[Expression]) {
+    for expression in expressions {
+        visitor.visit_expression(expression);
+    }
+}
+
+/// Rewriting of an expression, bottom-up by default.
+///
+/// Each method returns a whole expression, so a comparison can be replaced by a group and
+/// the other way around.
+pub trait Fold {
+    fn fold_expression(&mut self, expression: Expression) -> Expression {
+        fold_expression(self, expression)
+    }
+
+    fn fold_all(&mut self, expressions: Vec<Expression>) -> Expression {
+        Node::All(fold_expressions(self, expressions)).into()
+    }
+
+    fn fold_any_of(&mut self, expressions: Vec<Expression>) -> Expression {
+        Node::AnyOf(fold_expressions(self, expressions)).into()
+    }
+
+    fn fold_not(&mut self, expression: Expression) -> Expression {
+        Node::Not(Box::new(self.fold_expression(expression))).into()
+    }
+
+    fn fold_equal(&mut self, key: String, target: String) -> Expression {
+        Node::Equal(key, target).into()
+    }
+
+    fn fold_equal_ci(&mut self, key: String, target: String) -> Expression {
+        Node::EqualCI(key, target).into()
+    }
+
+    fn fold_greater(&mut self, key: String, target: String) -> Expression {
+        Node::Greater(key, target).into()
+    }
+
+    fn fold_less(&mut self, key: String, target: String) -> Expression {
+        Node::Less(key, target).into()
+    }
+
+    fn fold_greater_equal(&mut self, key: String, target: String) -> Expression {
+        Node::GreaterEqual(key, target).into()
+    }
+
+    fn fold_less_equal(&mut self, key: String, target: String) -> Expression {
+        Node::LessEqual(key, target).into()
+    }
+
+    fn fold_not_equal(&mut self, key: String, target: String) -> Expression {
+        Node::NotEqual(key, target).into()
+    }
+
+    fn fold_between(&mut self, key: String, bounds: Bounds) -> Expression {
+        Node::Between(key, bounds).into()
+    }
+
+    fn fold_contains(&mut self, key: String, target: String) -> Expression {
+        Node::Contains(key, target).into()
+    }
+
+    fn fold_starts_with(&mut self, key: String, target: String) -> Expression {
+        Node::StartsWith(key, target).into()
+    }
+
+    fn fold_ends_with(&mut self, key: String, target: String) -> Expression {
+        Node::EndsWith(key, target).into()
+    }
+
+    fn fold_wildcard(&mut self, key: String, target: String) -> Expression {
+        Node::Wildcard(key, target).into()
+    }
+
+    fn fold_regex(&mut self, key: String, target: String) -> Expression {
+        Node::Regex(key, target).into()
+    }
+
+    fn fold_regex_ci(&mut self, key: String, target: String) -> Expression {
+        Node::RegexCI(key, target).into()
+    }
+
+    fn fold_any(&mut self, key: String, targets: Vec<String>) -> Expression {
+        Node::Any(key, targets).into()
+    }
+
+    fn fold_null(&mut self, key: String) -> Expression {
+        Node::Null(key).into()
+    }
+}
+
+/// Dispatches `expression` to the method of `folder` for its node.
+pub fn fold_expression<F: Fold + ?Sized>(folder: &mut F, expression: Expression) -> Expression {
+    match expression.node {
+        Node::All(expressions) => folder.fold_all(expressions),
+        Node::AnyOf(expressions) => folder.fold_any_of(expressions),
+        Node::Not(expression) => folder.fold_not(*expression),
+        Node::Equal(key, target) => folder.fold_equal(key, target),
+        Node::EqualCI(key, target) => folder.fold_equal_ci(key, target),
+        Node::Greater(key, target) => folder.fold_greater(key, target),
+        Node::Less(key, target) => folder.fold_less(key, target),
+        Node::GreaterEqual(key, target) => folder.fold_greater_equal(key, target),
+        Node::LessEqual(key, target) => folder.fold_less_equal(key, target),
+        Node::NotEqual(key, target) => folder.fold_not_equal(key, target),
+        Node::Between(key, bounds) => folder.fold_between(key, bounds),
+        Node::Contains(key, target) => folder.fold_contains(key, target),
+        Node::StartsWith(key, target) => folder.fold_starts_with(key, target),
+        Node::EndsWith(key, target) => folder.fold_ends_with(key, target),
+        Node::Wildcard(key, target) => folder.fold_wildcard(key, target),
+        Node::Regex(key, target) => folder.fold_regex(key, target),
+        Node::RegexCI(key, target) => folder.fold_regex_ci(key, target),
+        Node::Any(key, targets) => folder.fold_any(key, targets),
+        Node::Null(key) => folder.fold_null(key),
+    }
+}
+
+/// Folds every expression in order.
+pub fn fold_expressions<F: Fold + ?Sized>(
+    folder: &mut F,
+    expressions: Vec<Expression>,
+) -> Vec<Expression> {
+    expressions
+        .into_iter()
+        .map(|expression| folder.fold_expression(expression))
+        .collect()
+}
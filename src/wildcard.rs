@@ -0,0 +1,63 @@
+//! Patterns of [`Node::Wildcard`](crate::Node::Wildcard), where `*` matches any run of
+//! characters, `?` any single one, and `\` makes the character after it literal, e.g.
+//! `"100\%"` as GSS is the pattern `100\%`, which only matches `100%`.
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Token {
+    Char(char),
+    AnyRun,
+    AnyChar,
+}
+
+pub fn tokens(pattern: &str) -> Vec<Token> {
+    let mut chars = pattern.chars();
+    let mut tokens = Vec::new();
+    while let Some(c) = chars.next() {
+        tokens.push(match c {
+            '*' => Token::AnyRun,
+            '?' => Token::AnyChar,
+            // A trailing `\` has nothing to escape, so it stands for itself.
+            '\\' => Token::Char(chars.next().unwrap_or('\\')),
+            c => Token::Char(c),
+        });
+    }
+    tokens
+}
+
+/// Whether `value` matches `pattern`, case-sensitively.
+pub fn matches(pattern: &str, value: &str) -> bool {
+    let pattern = tokens(pattern);
+    let value = value.chars().collect::<Vec<_>>();
+    let (mut p, mut v) = (0, 0);
+    // Where the last `*` was and how much of the value it has taken so far.
+    let mut star = None;
+    while v < value.len() {
+        match pattern.get(p) {
+            Some(Token::AnyChar) => {
+                p += 1;
+                v += 1;
+                continue;
+            }
+            Some(Token::Char(c)) if *c == value[v] => {
+                p += 1;
+                v += 1;
+                continue;
+            }
+            Some(Token::AnyRun) => {
+                star = Some((p, v));
+                p += 1;
+                continue;
+            }
+            _ => {}
+        }
+        match star {
+            Some((star_p, star_v)) => {
+                p = star_p + 1;
+                v = star_v + 1;
+                star = Some((star_p, star_v + 1));
+            }
+            None => return false,
+        }
+    }
+    pattern[p..].iter().all(|token| *token == Token::AnyRun)
+}
@@ -0,0 +1,190 @@
+use flp_gsp::{
+    analysis::{analyze, Analysis, Conflict, Implication, ValueType, ValueTypes},
+    Expression,
+};
+
+fn parse(s: &str) -> Expression {
+    Expression::try_from_str(s).unwrap()
+}
+
+fn types() -> ValueTypes {
+    let mut types = ValueTypes::new();
+    types.insert("age".into(), ValueType::Integer);
+    types.insert("score".into(), ValueType::Float);
+    types
+}
+
+fn analysis(s: &str) -> Analysis {
+    analyze(&parse(s), &types())
+}
+
+#[test]
+fn test_unsatisfiable() {
+    assert_eq!(
+        analysis(r#"("age" > "30" & "age" < "20")"#),
+        Analysis::Unsatisfiable(vec![Conflict {
+            key: "age".into(),
+            expressions: vec![parse(r#""age" > "30""#), parse(r#""age" < "20""#)],
+        }])
+    );
+    assert_eq!(
+        analysis(r#"("sex" = "male" & "name" - & "sex" = "female")"#),
+        Analysis::Unsatisfiable(vec![Conflict {
+            key: "sex".into(),
+            expressions: vec![parse(r#""sex" = "male""#), parse(r#""sex" = "female""#)],
+        }])
+    );
+
+    let unsatisfiable = |s| matches!(analysis(s), Analysis::Unsatisfiable(_));
+    assert!(unsatisfiable(r#""age" > "3" & "age" < "4""#));
+    assert!(unsatisfiable(
+        r#""age" >= "4" & "age" <= "6" & "age" != "5" & !"age" ? ["4", "6"]"#
+    ));
+    assert!(unsatisfiable(r#""age" != "5" & !"age" != "5""#));
+    assert!(unsatisfiable(
+        r#""age" .. ["1", "3") & "age" .. ("3", "5"]"#
+    ));
+    assert!(unsatisfiable(r#""score" .. ["1", "3") & "score" >= "3""#));
+    assert!(unsatisfiable(r#""name" ? ["Jack", "Joe"] & "name" *= "x""#));
+    assert!(unsatisfiable(
+        r#""age" > "3" & "age" < "6" & !"age" ? ["4", "5"]"#
+    ));
+    assert!(unsatisfiable(r#""score" > "3.5" & !"score" > "3.5""#));
+    assert!(unsatisfiable(r#""name" - & "name" * "J*""#));
+    // A missing key is `NULL`, not the text "null" the evaluator takes for one.
+    assert!(unsatisfiable(r#""name" - & "name" = "null""#));
+    assert!(unsatisfiable(
+        r#""name" ? ["a", "b"] & !"name" = "a" & !"name" = "b""#
+    ));
+    assert!(unsatisfiable(r#"("age" > "30" | "x" ? []) & "age" < "20""#));
+    assert!(unsatisfiable(
+        r#"("a" = "1" | "a" = "2") & ("a" = "3" | "a" = "4")"#
+    ));
+}
+
+#[test]
+fn test_tautology() {
+    assert_eq!(analysis(r#""age" - | !"age" -"#), Analysis::Tautology);
+    assert_eq!(
+        analysis(r#""age" > "10" | !"age" > "20""#),
+        Analysis::Tautology
+    );
+    assert_eq!(analysis(r#"!"x" ? []"#), Analysis::Tautology);
+}
+
+#[test]
+fn test_satisfiable() {
+    assert_eq!(
+        analysis(r#""age" > "3" & "age" < "5""#),
+        Analysis::Satisfiable
+    );
+    assert_eq!(
+        analysis(r#""score" > "3" & "score" < "3.1""#),
+        Analysis::Satisfiable
+    );
+    assert_eq!(
+        analysis(r#""name" > "a" & "name" < "b" & "name" * "a*""#),
+        Analysis::Satisfiable
+    );
+    assert_eq!(
+        analysis(r#""age" > "30" & "age" < "20" | "sex" = "male""#),
+        Analysis::Satisfiable
+    );
+    // Without types, "10" < "9" as text.
+    assert_eq!(
+        analyze(&parse(r#""age" > "9" & "age" < "10""#), &ValueTypes::new()),
+        Analysis::Unsatisfiable(vec![Conflict {
+            key: "age".into(),
+            expressions: vec![parse(r#""age" > "9""#), parse(r#""age" < "10""#)],
+        }])
+    );
+}
+
+#[test]
+fn test_unknown() {
+    assert_eq!(
+        analysis(r#""name" $ "^a" & "name" $ "^b""#),
+        Analysis::Unknown
+    );
+    assert_eq!(analysis(r#""age" > "x""#), Analysis::Unknown);
+    // Tautologies the negation of which cannot be decided either.
+    for s in [
+        r#""name" *= "a" | !"name" *= "a""#,
+        r#""name" ^= "a" | !"name" ^= "a""#,
+        r#""name" $ "^a" | !"name" $ "^a""#,
+    ] {
+        assert_eq!(analysis(s), Analysis::Unknown, "{}", s);
+    }
+
+    // Too many candidates to try against too many targets.
+    let targets = (1..=1000)
+        .map(|n| format!("\"{}\"", n))
+        .collect::<Vec<_>>()
+        .join(", ");
+    assert_eq!(
+        analysis(&format!(r#""score" >= "0.5" & !"score" ? [{}]"#, targets)),
+        Analysis::Unknown
+    );
+    // An empty range needs no candidates at all.
+    assert!(matches!(
+        analysis(&format!(
+            r#""score" > "5" & "score" < "1" & !"score" ? [{}]"#,
+            targets
+        )),
+        Analysis::Unsatisfiable(_)
+    ));
+}
+
+#[test]
+fn test_implies() {
+    let implies = |a: &str, b: &str| parse(a).implies(&parse(b), &types());
+    assert_eq!(
+        implies(r#""age" > "30" & "sex" = "male""#, r#""age" > "18""#),
+        Implication::Yes
+    );
+    assert_eq!(
+        implies(r#""age" > "18""#, r#""age" > "30" & "sex" = "male""#),
+        Implication::No
+    );
+    assert_eq!(
+        implies(
+            r#""sex" ? ["male"] | "sex" = "female""#,
+            r#""sex" ? ["female", "male", "other"]"#
+        ),
+        Implication::Yes
+    );
+    // SQL leaves `NOT age > 18` unknown for a `NULL` age.
+    assert_eq!(implies(r#""age" -"#, r#"!"age" > "18""#), Implication::No);
+    assert_eq!(
+        implies(r#""age" < "18""#, r#"!"age" > "18""#),
+        Implication::Yes
+    );
+    assert_eq!(
+        implies(r#""age" < "18""#, r#"!("age" > "18" | "sex" = "male")"#),
+        Implication::No
+    );
+    assert_eq!(
+        implies(
+            r#""age" < "18" & "sex" -"#,
+            r#"!("age" > "18" & "sex" = "male")"#
+        ),
+        Implication::Yes
+    );
+    assert_eq!(implies(r#""age" < "18""#, r#"!"age" -"#), Implication::Yes);
+    assert_eq!(
+        implies(r#""age" = "18""#, r#""age" ? ["17", "19"]"#),
+        Implication::No
+    );
+    assert_eq!(
+        implies(r#""name" $ "^a""#, r#""name" $ "^a.*""#),
+        Implication::Unknown
+    );
+    assert_eq!(
+        implies(r#""name" = "Jack""#, r#""name" $~ "^j""#),
+        Implication::Yes
+    );
+    assert_eq!(
+        implies(r#""name" = "Jack""#, r#""name" $ "^j""#),
+        Implication::Unknown
+    );
+}
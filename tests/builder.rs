@@ -0,0 +1,28 @@
+use flp_gsp::{builder::field, Expression};
+
+#[test]
+fn test_builder() {
+    let expression = field("age")
+        .gt("18")
+        .and(field("sex").any_of(["male", "female"]))
+        | !field("name").wildcard("J?c*") & field("name").eq_ci("a\"b");
+    assert_eq!(
+        expression.to_string(),
+        r#""age" > "18" & "sex" ? ["male", "female"] | !"name" * "J?c*" & "name" ~ "a\"b""#
+    );
+
+    let parsed = Expression::try_from_str(&expression.to_string()).unwrap();
+    assert_eq!(parsed, expression);
+}
+
+#[test]
+fn test_combine() {
+    let user = Expression::try_from_str(r#""a" = "1" & ("b" - | "c" < "2")"#).unwrap();
+    let expression = user & field("tenant").eq("42") & field("deleted").is_null();
+
+    let expected = Expression::try_from_str(
+        r#""a" = "1" & ("b" - | "c" < "2") & "tenant" = "42" & "deleted" -"#,
+    )
+    .unwrap();
+    assert_eq!(expression, expected);
+}
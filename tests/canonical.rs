@@ -0,0 +1,35 @@
+use std::collections::HashMap;
+
+use flp_gsp::Expression;
+
+fn parse(s: &str) -> Expression {
+    Expression::try_from_str(s).unwrap()
+}
+
+#[test]
+fn test_canonical() {
+    let a = parse(r#"("a" = "1" & "b" = "2")"#);
+    let b = parse(r#"("b" = "2" & "a" = "1")"#);
+    assert_ne!(a, b);
+    assert_eq!(a.canonical(), b.canonical());
+    assert_eq!(a.canonical_hash(), b.canonical_hash());
+
+    let a = parse(r#""x" ? ["2", "1", "2"] | !("c" - & "d" - & "c" -) | "x" ? ["1", "2"]"#);
+    let b = parse(r#"!("d" - & "c" -) | "x" ? ["1", "2"]"#);
+    assert_eq!(a.canonical(), b.canonical());
+    assert_eq!(a.canonical_hash(), b.canonical_hash());
+
+    let c = parse(r#"!("d" - | "c" -) | "x" ? ["1", "2"]"#);
+    assert_ne!(a.canonical(), c.canonical());
+}
+
+#[test]
+fn test_cache_key() {
+    let mut cache = HashMap::new();
+    cache.insert(parse(r#""a" = "1" & "b" = "2""#).canonical(), 42);
+
+    let query = parse(r#""b" = "2" & "a" = "1""#);
+    let copy = query.clone();
+    assert_eq!(copy, query);
+    assert_eq!(cache.get(&copy.canonical()), Some(&42));
+}
@@ -0,0 +1,84 @@
+#![cfg(feature = "clickhouse")]
+
+use flp_gsp::{interpreter::clickhouse::*, Expression};
+use rust_decimal::Decimal;
+
+#[test]
+fn test_clickhouse() {
+    let s = r#"((((! "age" -) & (! "age" > "18")) & ("sex" ? ["male", "Male"] | "sex" ~ "Female")) & "name" * "J?c*")"#;
+    let expression = Expression::try_from_str(s).unwrap();
+
+    let mut renames = ClickhouseRenames::new();
+    renames.insert("name".into(), "t.name".into());
+    renames.insert("sex".into(), "gender".into());
+
+    let mut types = ClickhouseTypes::new();
+    types.insert("age".into(), ClickhouseType::UInt8(None));
+    types.insert("sex".into(), ClickhouseType::StringLike(None));
+    types.insert("name".into(), ClickhouseType::StringLike(None));
+
+    let interpreted = interpret(&expression, &renames, &types, 1).unwrap();
+    let (clause, binds) = interpreted;
+
+    assert_eq!(
+        clause,
+        "((NOT `age` IS NULL) AND (NOT `age` > {p1:UInt8}) AND (has({p2:Array(String)}, `gender`) OR `gender` ILIKE {p3:String}) AND `t`.`name` LIKE {p4:String})"
+    );
+    assert_eq!(
+        binds,
+        vec![
+            ClickhouseType::UInt8(Some(18)),
+            ClickhouseType::Array(vec![
+                ClickhouseType::StringLike(Some("male".into())),
+                ClickhouseType::StringLike(Some("Male".into()))
+            ]),
+            ClickhouseType::StringLike(Some("Female".into())),
+            ClickhouseType::StringLike(Some("J_c%".into()))
+        ]
+    );
+}
+
+#[test]
+fn test_clickhouse_types() {
+    let s = r#""price" .. ["9.5", "20.25"] & "price" ? ["1.5", "2.25"] & "day" = "2024-02-29" & ("name" $ "^J" | "name" $~ "k$" | "a`b" *= "50%")"#;
+    let expression = Expression::try_from_str(s).unwrap();
+
+    let mut types = ClickhouseTypes::new();
+    types.insert(
+        "price".into(),
+        ClickhouseType::Decimal {
+            precision: 18,
+            scale: 2,
+            value: None,
+        },
+    );
+    types.insert("day".into(), ClickhouseType::Date(None));
+    types.insert("name".into(), ClickhouseType::StringLike(None));
+    types.insert("a`b".into(), ClickhouseType::StringLike(None));
+
+    let price = |value| ClickhouseType::Decimal {
+        precision: 18,
+        scale: 2,
+        value: Some(value),
+    };
+    let (clause, binds) = interpret(&expression, &ClickhouseRenames::new(), &types, 1).unwrap();
+    assert_eq!(
+        clause,
+        r"(`price` BETWEEN {p1:Decimal(18, 2)} AND {p2:Decimal(18, 2)} AND has({p3:Array(Decimal(18, 2))}, `price`) AND `day` = {p4:Date} AND (match(`name`, {p5:String}) OR match(`name`, {p6:String}) OR `a\`b` LIKE {p7:String}))"
+    );
+    assert_eq!(
+        binds,
+        vec![
+            price(Decimal::new(95, 1)),
+            price(Decimal::new(2025, 2)),
+            ClickhouseType::Array(vec![
+                price(Decimal::new(15, 1)),
+                price(Decimal::new(225, 2))
+            ]),
+            ClickhouseType::Date(Some("2024-02-29".parse().unwrap())),
+            ClickhouseType::StringLike(Some("^J".into())),
+            ClickhouseType::StringLike(Some("(?i)k$".into())),
+            ClickhouseType::StringLike(Some(r"%50\%%".into()))
+        ]
+    );
+}
@@ -3,6 +3,7 @@ pub enum Sex {
     Female,
     Other,
 }
+#[allow(clippy::from_over_into)]
 impl Into<String> for Sex {
     fn into(self) -> String {
         match self {
@@ -0,0 +1,62 @@
+use flp_gsp::{Expression, Node};
+
+fn assert_round_trip(s: &str) {
+    let expression = Expression::try_from_str(s).unwrap();
+    for printed in [format!("{}", expression), format!("{:#}", expression)] {
+        let reparsed = Expression::try_from_str(&printed).unwrap();
+        assert_eq!(reparsed, expression);
+    }
+}
+
+#[test]
+fn test_display() {
+    let s = r#"((((! "age" -) & (! "age" > "18")) & ("sex" ? ["male", "Male"] | "sex" ~ "Female")) & "\"name\"" * "J?c*")"#;
+    let expression = Expression::try_from_str(s).unwrap();
+
+    assert_eq!(
+        expression.to_string(),
+        r#"!"age" - & !"age" > "18" & ("sex" ? ["male", "Male"] | "sex" ~ "Female") & "\"name\"" * "J?c*""#
+    );
+    assert_eq!(
+        format!("{:#}", expression),
+        r#"!"age" -
+& !"age" > "18"
+& (
+    "sex" ? ["male", "Male"]
+    | "sex" ~ "Female"
+)
+& "\"name\"" * "J?c*""#
+    );
+}
+
+#[test]
+fn test_round_trip() {
+    assert_round_trip(r#""a" = "1""#);
+    assert_round_trip(r#"("a\\b" $ "^\"x\"$" | "c" < "2") & "d" ? []"#);
+    assert_round_trip(r#""a" $~ "^j" & "b" $ "k$""#);
+    assert_round_trip(r#"!(!("a" - | "b" -) & "c" ~ "3") | ! ! "d" * "?""#);
+    assert_round_trip(
+        r#"("a" = "1" | ("b" = "2" & ("c" = "3" | ("d" = "4" & !("e" = "5" | "f" = "6")))))"#,
+    );
+}
+
+#[test]
+fn test_unparsable_shapes() {
+    let equal = |key: &str| Expression::from(Node::Equal(key.into(), "1".into()));
+    let single = Expression::from(Node::AnyOf(vec![equal("a")]));
+    let nested = Expression::from(Node::All(vec![
+        Node::All(vec![equal("a"), equal("b")]).into(),
+        equal("c"),
+    ]));
+
+    assert_eq!(single.to_string(), r#""a" = "1""#);
+    assert_eq!(
+        Expression::try_from_str(&single.to_string()).unwrap(),
+        equal("a")
+    );
+    assert_eq!(nested.to_string(), r#"("a" = "1" & "b" = "1") & "c" = "1""#);
+    assert_eq!(
+        Expression::try_from_str(&nested.to_string()).unwrap(),
+        Node::All(vec![equal("a"), equal("b"), equal("c")]).into()
+    );
+}
@@ -0,0 +1,73 @@
+#![cfg(feature = "duckdb")]
+
+use flp_gsp::{interpreter::duckdb::*, Expression};
+use rust_decimal::Decimal;
+
+#[test]
+fn test_duckdb() {
+    let s = r#"((((! "age" -) & (! "age" > "18")) & ("sex" ? ["male", "Male"] | "sex" ~ "Female")) & "name" * "J?c*")"#;
+    let expression = Expression::try_from_str(s).unwrap();
+
+    let mut renames = DuckdbRenames::new();
+    renames.insert("name".into(), "t.name".into());
+    renames.insert("sex".into(), "gender".into());
+
+    let mut types = DuckdbTypes::new();
+    types.insert("age".into(), DuckdbType::Integer(None));
+    types.insert("sex".into(), DuckdbType::StringLike(None));
+    types.insert("name".into(), DuckdbType::StringLike(None));
+
+    let interpreted = interpret(&expression, &renames, &types, 1).unwrap();
+    let (clause, binds) = interpreted;
+
+    assert_eq!(
+        clause,
+        r#"((NOT "age" IS NULL) AND (NOT "age" > $1) AND (list_contains($2, "gender") OR "gender" ILIKE $3 ESCAPE '\') AND "t"."name" LIKE $4 ESCAPE '\')"#
+    );
+    assert_eq!(
+        binds,
+        vec![
+            DuckdbType::Integer(Some(18)),
+            DuckdbType::List(vec![
+                DuckdbType::StringLike(Some("male".into())),
+                DuckdbType::StringLike(Some("Male".into()))
+            ]),
+            DuckdbType::StringLike(Some("Female".into())),
+            DuckdbType::StringLike(Some("J_c%".into()))
+        ]
+    );
+}
+
+#[test]
+fn test_duckdb_types() {
+    let s = r#""price" >= "9.5" & "at" < "2024-02-29T12:00:00" & ("name" $ "^J" | "name" $~ "k$" | "name" ? [])"#;
+    let expression = Expression::try_from_str(s).unwrap();
+
+    let mut types = DuckdbTypes::new();
+    types.insert("price".into(), DuckdbType::Decimal(None));
+    types.insert("at".into(), DuckdbType::Timestamp(None));
+    types.insert("name".into(), DuckdbType::StringLike(None));
+
+    let (clause, binds) = interpret(&expression, &DuckdbRenames::new(), &types, 2).unwrap();
+    assert_eq!(
+        clause,
+        r#"("price" >= $2 AND "at" < $3 AND (regexp_matches("name", $4) OR regexp_matches("name", $5, 'i') OR FALSE))"#
+    );
+    assert_eq!(
+        binds,
+        vec![
+            DuckdbType::Decimal(Some(Decimal::new(95, 1))),
+            DuckdbType::Timestamp(Some("2024-02-29T12:00:00".parse().unwrap())),
+            DuckdbType::StringLike(Some("^J".into())),
+            DuckdbType::StringLike(Some("k$".into()))
+        ]
+    );
+
+    let mut types = DuckdbTypes::new();
+    types.insert("tags".into(), DuckdbType::List(vec![]));
+    let expression = Expression::try_from_str(r#""tags" = "a""#).unwrap();
+    assert!(matches!(
+        interpret(&expression, &DuckdbRenames::new(), &types, 1),
+        Err(Error::ListTarget)
+    ));
+}
@@ -7,6 +7,7 @@ mod common;
 use common::*;
 
 #[test]
+#[allow(clippy::field_reassign_with_default)]
 fn test_evaluate() {
     let s = r#"(((! "age" > "18") & ("sex" ? ["male", "Male"] | "sex" ~ "Female")) & "name" * "J?c*")"#;
     let expression = Expression::try_from_str(s).unwrap();
@@ -76,3 +77,93 @@ fn test_invalid() {
     let expression = Expression::try_from_str(s);
     assert!(expression.is_err());
 }
+
+#[test]
+fn test_evaluate_comparisons() {
+    let s = r#""age" >= "9" & "age" <= "18" & "sex" != "Other" & !"age" .. ("10", "12"]"#;
+    let expression = Expression::try_from_str(s).unwrap();
+
+    let mut rules = EvaluateRules::new();
+    rules.insert(
+        "age".into(),
+        EvaluateRule {
+            is_greater_than: |value, target| {
+                value.parse::<u8>().unwrap() > target.parse::<u8>().unwrap()
+            },
+            is_less_than: |value, target| {
+                value.parse::<u8>().unwrap() < target.parse::<u8>().unwrap()
+            },
+            ..Default::default()
+        },
+    );
+    rules.insert("sex".into(), EvaluateRule::default());
+
+    let matches = |age: u8, sex: Sex| {
+        let mut pairs = EvaluatePairs::new();
+        pairs.insert("age".into(), age.to_string());
+        pairs.insert("sex".into(), sex.into());
+        interpret(&expression, &rules, &pairs)
+    };
+    assert!(matches(9, Sex::Male));
+    assert!(matches(18, Sex::Female));
+    assert!(!matches(8, Sex::Male));
+    assert!(!matches(100, Sex::Male));
+    assert!(!matches(10, Sex::Other));
+    assert!(matches(10, Sex::Male));
+    assert!(!matches(11, Sex::Male));
+    assert!(!matches(12, Sex::Male));
+}
+
+#[test]
+fn test_evaluate_string_operators() {
+    let s = r#""name" *= "ac" & "name" ^= "J" & !"name" $= "%""#;
+    let expression = Expression::try_from_str(s).unwrap();
+
+    let mut rules = EvaluateRules::new();
+    rules.insert("name".into(), EvaluateRule::default());
+
+    let matches = |name: &str| {
+        let mut pairs = EvaluatePairs::new();
+        pairs.insert("name".into(), name.into());
+        interpret(&expression, &rules, &pairs)
+    };
+    assert!(matches("Jack"));
+    assert!(!matches("jack"));
+    assert!(!matches("Jac%"));
+    assert!(!matches("Joe"));
+}
+
+#[test]
+fn test_evaluate_wildcard_escaping() {
+    let s = r#""name" * "\\*?*\\?""#;
+    let expression = Expression::try_from_str(s).unwrap();
+
+    let mut rules = EvaluateRules::new();
+    rules.insert("name".into(), EvaluateRule::default());
+
+    let matches = |name: &str| {
+        let mut pairs = EvaluatePairs::new();
+        pairs.insert("name".into(), name.into());
+        interpret(&expression, &rules, &pairs)
+    };
+    assert!(matches("*a?"));
+    assert!(matches("*abc?"));
+    assert!(!matches("xa?"));
+    assert!(!matches("*a"));
+}
+
+#[test]
+fn test_evaluate_regex() {
+    let mut rules = EvaluateRules::new();
+    rules.insert("name".into(), EvaluateRule::default());
+
+    let matches = |s: &str, name: &str| {
+        let mut pairs = EvaluatePairs::new();
+        pairs.insert("name".into(), name.into());
+        interpret(&Expression::try_from_str(s).unwrap(), &rules, &pairs)
+    };
+    assert!(matches(r#""name" $ "^J.*k$""#, "Jack"));
+    assert!(!matches(r#""name" $ "^j""#, "Jack"));
+    assert!(matches(r#""name" $~ "^j""#, "Jack"));
+    assert!(!matches(r#""name" $~ "^j$""#, "Jack"));
+}
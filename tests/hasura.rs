@@ -1,6 +1,6 @@
 #![cfg(feature = "hasura")]
 
-use flp_gsp::{interpreter::hasura::*, Expression};
+use flp_gsp::{interpreter::hasura::*, Expression, Node};
 
 #[test]
 fn test_sqlite() {
@@ -17,6 +17,69 @@ fn test_sqlite() {
 
     assert_eq!(
         clause,
-        "{_and:[{_and:[{_and:[{_not:{age:{_is_null:true}}},{_not:{age:{_gt:18}}}]},{_or:[{sex:{_in:[\"male\",\"Male\"]}},{sex:{_ilike:\"Female\"}}]}]},{name:{_ilike:\"J_c%\"}}]}"
+        "{_and:[{_not:{age:{_is_null:true}}},{_not:{age:{_gt:18}}},{_or:[{sex:{_in:[\"male\",\"Male\"]}},{sex:{_ilike:\"Female\"}}]},{name:{_like:\"J_c%\"}}]}"
+    );
+}
+
+#[test]
+fn test_hasura_comparisons() {
+    let s = r#""age" >= "18" & "age" <= "65" & "sex" != "Other" & "age" .. ["20", "30")"#;
+    let expression = Expression::try_from_str(s).unwrap();
+
+    let mut types = HasuraTypes::new();
+    types.insert("age".into(), HasuraType::Integer);
+    types.insert("sex".into(), HasuraType::StringLike);
+
+    assert_eq!(
+        interpret(&expression, &types).unwrap(),
+        "{_and:[{age:{_gte:18}},{age:{_lte:65}},{sex:{_neq:\"Other\"}},{age:{_gte:20,_lt:30}}]}"
+    );
+}
+
+#[test]
+fn test_hasura_string_operators() {
+    let s = r#""name" *= "50%" | "name" ^= "\"J" | "name" $= "k""#;
+    let expression = Expression::try_from_str(s).unwrap();
+
+    let mut types = HasuraTypes::new();
+    types.insert("name".into(), HasuraType::StringLike);
+
+    assert_eq!(
+        interpret(&expression, &types).unwrap(),
+        r#"{_or:[{name:{_like:"%50\\%%"}},{name:{_like:"\"J%"}},{name:{_like:"%k"}}]}"#
+    );
+}
+
+#[test]
+fn test_hasura_escaping() {
+    let s = r#""name" ~ "a_b" | "name" * "\\*%*""#;
+    let expression = Expression::try_from_str(s).unwrap();
+
+    let mut types = HasuraTypes::new();
+    types.insert("name".into(), HasuraType::StringLike);
+
+    assert_eq!(
+        interpret(&expression, &types).unwrap(),
+        r#"{_or:[{name:{_ilike:"a\\_b"}},{name:{_like:"*\\%%"}}]}"#
+    );
+
+    let expression = Expression::from(Node::Equal("name".into(), "a\n\"b\"\t\r\u{1}".into()));
+    assert_eq!(
+        interpret(&expression, &types).unwrap(),
+        r#"{name:{_eq:"a\n\"b\"\t\r\u0001"}}"#
+    );
+}
+
+#[test]
+fn test_hasura_regex() {
+    let s = r#""name" $ "^J.*" | "name" $~ "k$""#;
+    let expression = Expression::try_from_str(s).unwrap();
+
+    let mut types = HasuraTypes::new();
+    types.insert("name".into(), HasuraType::StringLike);
+
+    assert_eq!(
+        interpret(&expression, &types).unwrap(),
+        r#"{_or:[{name:{_regex:"^J.*"}},{name:{_iregex:"k$"}}]}"#
     );
 }
@@ -0,0 +1,32 @@
+#![cfg(all(feature = "evaluate", feature = "postgres"))]
+
+use flp_gsp::{
+    interpreter::{evaluate::*, postgres::*, Interpreter},
+    Expression,
+};
+
+fn run<I: Interpreter>(interpreter: &I, context: I::Context<'_>) -> I::Output
+where
+    I::Error: std::fmt::Debug,
+{
+    let expression = Expression::try_from_str(r#""age" > "18" & !"name" -"#).unwrap();
+    interpreter.interpret(&expression, context).unwrap()
+}
+
+#[test]
+fn test_interpreter() {
+    let mut rules = EvaluateRules::new();
+    rules.insert("age".into(), EvaluateRule::default());
+    rules.insert("name".into(), EvaluateRule::default());
+    let mut pairs = EvaluatePairs::new();
+    pairs.insert("age".into(), "20".into());
+    pairs.insert("name".into(), "Jack".into());
+    assert!(run(&Evaluate, (&rules, &pairs)));
+
+    let mut types = PostgresTypes::new();
+    types.insert("age".into(), PostgresType::Int(None));
+    types.insert("name".into(), PostgresType::StringLike(None));
+    let (clause, binds) = run(&Postgres, (&PostgresRenames::new(), &types, 3));
+    assert_eq!(clause, r#"("age" > $3 AND (NOT "name" IS NULL))"#);
+    assert_eq!(binds, vec![PostgresType::Int(Some(18))]);
+}
@@ -0,0 +1,97 @@
+#![cfg(feature = "mssql")]
+
+use flp_gsp::{
+    interpreter::{mssql::*, Interpreter},
+    Expression,
+};
+
+#[test]
+fn test_mssql() {
+    let s = r#"((((! "age" -) & (! "age" > "18")) & ("sex" ? ["male", "Male"] | "sex" ~ "Female")) & "name" * "J?c*")"#;
+    let expression = Expression::try_from_str(s).unwrap();
+
+    let mut renames = MssqlRenames::new();
+    renames.insert("name".into(), "t.name".into());
+    renames.insert("sex".into(), "gender".into());
+
+    let mut types = MssqlTypes::new();
+    types.insert("age".into(), MssqlType::Int(None));
+    types.insert("sex".into(), MssqlType::StringLike(None));
+    types.insert("name".into(), MssqlType::StringLike(None));
+
+    let interpreted = interpret(&expression, &renames, &types, 1).unwrap();
+    let (clause, binds) = interpreted;
+
+    assert_eq!(
+        clause,
+        r"((NOT [age] IS NULL) AND (NOT [age] > @p1) AND ([gender] IN (@p2, @p3) OR [gender] COLLATE Latin1_General_CI_AS LIKE @p4 ESCAPE '\') AND [t].[name] COLLATE Latin1_General_CS_AS LIKE @p5 ESCAPE '\')"
+    );
+    assert_eq!(
+        binds,
+        vec![
+            MssqlType::Int(Some(18)),
+            MssqlType::StringLike(Some("male".into())),
+            MssqlType::StringLike(Some("Male".into())),
+            MssqlType::StringLike(Some("Female".into())),
+            MssqlType::StringLike(Some("J_c%".into()))
+        ]
+    );
+}
+
+#[test]
+fn test_mssql_comparisons() {
+    let s = r#"("age" >= "18" & "age" .. ("20", "30"] & "sex" != "Other" & "sex" ? []) | "a]b" $~ "^x""#;
+    let expression = Expression::try_from_str(s).unwrap();
+
+    let mut types = MssqlTypes::new();
+    types.insert("age".into(), MssqlType::TinyInt(None));
+    types.insert("sex".into(), MssqlType::StringLike(None));
+    types.insert("a]b".into(), MssqlType::StringLike(None));
+
+    // `REGEXP_LIKE` is opt-in.
+    assert!(matches!(
+        interpret(&expression, &MssqlRenames::new(), &types, 3),
+        Err(Error::Unsupported(_))
+    ));
+
+    let mssql = Mssql { regexp_like: true };
+    let (clause, binds) = mssql
+        .interpret(&expression, (&MssqlRenames::new(), &types, 3))
+        .unwrap();
+    assert_eq!(
+        clause,
+        "(([age] >= @p3 AND ([age] > @p4 AND [age] <= @p5) AND [sex] <> @p6 AND 1=0) OR REGEXP_LIKE([a]]b], @p7, 'i'))"
+    );
+    assert_eq!(
+        binds,
+        vec![
+            MssqlType::TinyInt(Some(18)),
+            MssqlType::TinyInt(Some(20)),
+            MssqlType::TinyInt(Some(30)),
+            MssqlType::StringLike(Some("Other".into())),
+            MssqlType::StringLike(Some("^x".into()))
+        ]
+    );
+}
+
+#[test]
+fn test_mssql_like_escaping() {
+    let s = r#""name" *= "[50%]" & "name" * "a[_]*""#;
+    let expression = Expression::try_from_str(s).unwrap();
+
+    let mut types = MssqlTypes::new();
+    types.insert("name".into(), MssqlType::StringLike(None));
+
+    let (clause, binds) = interpret(&expression, &MssqlRenames::new(), &types, 1).unwrap();
+    assert_eq!(
+        clause,
+        r"([name] COLLATE Latin1_General_CS_AS LIKE @p1 ESCAPE '\' AND [name] COLLATE Latin1_General_CS_AS LIKE @p2 ESCAPE '\')"
+    );
+    assert_eq!(
+        binds,
+        vec![
+            MssqlType::StringLike(Some(r"%\[50\%]%".into())),
+            MssqlType::StringLike(Some(r"a\[\_]%".into()))
+        ]
+    );
+}
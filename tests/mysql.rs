@@ -1,6 +1,9 @@
 #![cfg(feature = "mysql")]
 
-use flp_gsp::{interpreter::mysql::*, Expression};
+use flp_gsp::{
+    interpreter::{mysql::*, Rename},
+    Expression,
+};
 
 #[test]
 fn test_mysql() {
@@ -21,7 +24,7 @@ fn test_mysql() {
 
     assert_eq!(
         clause,
-        "((((NOT age IS NULL) AND (NOT age > ?)) AND (gender IN (?, ?) OR gender LIKE ?)) AND t.name LIKE ?)"
+        r"((NOT `age` IS NULL) AND (NOT `age` > ?) AND (`gender` IN (?, ?) OR `gender` LIKE ? ESCAPE '\\') AND `t`.`name` LIKE ? ESCAPE '\\')"
     );
     assert_eq!(
         binds,
@@ -34,3 +37,70 @@ fn test_mysql() {
         ]
     );
 }
+
+#[test]
+fn test_mysql_string_operators() {
+    let s = r#""name" *= "50%_off" & "name" ^= "a\\b" & "name" $= "!""#;
+    let expression = Expression::try_from_str(s).unwrap();
+
+    let mut types = MysqlTypes::new();
+    types.insert("name".into(), MysqlType::StringLike(None));
+
+    let (clause, binds) = interpret(&expression, &MysqlRenames::new(), &types).unwrap();
+    assert_eq!(
+        clause,
+        r"(`name` LIKE ? ESCAPE '\\' AND `name` LIKE ? ESCAPE '\\' AND `name` LIKE ? ESCAPE '\\')"
+    );
+    assert_eq!(
+        binds,
+        vec![
+            MysqlType::StringLike(Some(r"%50\%\_off%".into())),
+            MysqlType::StringLike(Some(r"a\\b%".into())),
+            MysqlType::StringLike(Some("%!".into()))
+        ]
+    );
+}
+
+#[test]
+fn test_mysql_regex() {
+    let s = r#""name" $ "^J.*" | "name" $~ "k$""#;
+    let expression = Expression::try_from_str(s).unwrap();
+
+    let mut types = MysqlTypes::new();
+    types.insert("name".into(), MysqlType::StringLike(None));
+
+    let (clause, binds) = interpret(&expression, &MysqlRenames::new(), &types).unwrap();
+    assert_eq!(clause, "(`name` REGEXP ? OR REGEXP_LIKE(`name`, ?, 'i'))");
+    assert_eq!(
+        binds,
+        vec![
+            MysqlType::StringLike(Some("^J.*".into())),
+            MysqlType::StringLike(Some("k$".into()))
+        ]
+    );
+}
+
+#[test]
+fn test_mysql_identifiers() {
+    let s = r#""a`b" = "1" & "name" ~ "J" & "age" -"#;
+    let expression = Expression::try_from_str(s).unwrap();
+
+    let mut renames = MysqlRenames::new();
+    renames.insert("name".into(), Rename::Raw("LOWER(t.name)".into()));
+
+    let mut types = MysqlTypes::new();
+    types.insert("a`b".into(), MysqlType::Int(None));
+    types.insert("name".into(), MysqlType::StringLike(None));
+
+    assert!(matches!(
+        interpret(&expression, &renames, &types),
+        Err(Error::UnknownKey(key)) if key == "age"
+    ));
+
+    types.insert("age".into(), MysqlType::Int(None));
+    let (clause, _) = interpret(&expression, &renames, &types).unwrap();
+    assert_eq!(
+        clause,
+        r"(`a``b` = ? AND LOWER(t.name) LIKE ? ESCAPE '\\' AND `age` IS NULL)"
+    );
+}
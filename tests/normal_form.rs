@@ -0,0 +1,52 @@
+use flp_gsp::{Expression, Node, NormalFormError};
+
+fn parse(s: &str) -> Expression {
+    Expression::try_from_str(s).unwrap()
+}
+
+#[test]
+fn test_dnf() {
+    let dnf = parse(r#"("a" - | "b" -) & !("c" - & "d" -)"#)
+        .to_dnf()
+        .unwrap();
+    assert_eq!(
+        dnf.to_string(),
+        r#""a" - & !"c" - | "a" - & !"d" - | "b" - & !"c" - | "b" - & !"d" -"#
+    );
+    match dnf.node {
+        Node::AnyOf(clauses) => {
+            assert_eq!(clauses.len(), 4);
+            assert!(clauses
+                .iter()
+                .all(|clause| matches!(&clause.node, Node::All(l) if l.len() == 2)));
+        }
+        _ => panic!("expected a disjunction"),
+    }
+
+    let dnf = parse(r#""a" - & "b" -"#).to_dnf().unwrap();
+    assert!(matches!(dnf.node, Node::AnyOf(clauses) if clauses.len() == 1));
+}
+
+#[test]
+fn test_cnf() {
+    let cnf = parse(r#""a" - & "b" - | "c" - & "a" -"#).to_cnf().unwrap();
+    assert_eq!(
+        cnf.to_string(),
+        r#"("a" - | "c" -) & "a" - & ("b" - | "c" -) & ("b" - | "a" -)"#
+    );
+}
+
+#[test]
+fn test_blow_up() {
+    let s = (0..12)
+        .map(|i| format!(r#"("a{}" - | "b{}" -)"#, i, i))
+        .collect::<Vec<_>>()
+        .join(" & ");
+    let expression = parse(&s);
+    assert_eq!(
+        expression.to_dnf().unwrap_err(),
+        NormalFormError { max: 1024 }
+    );
+    assert!(expression.to_dnf_with(4096).is_ok());
+    assert!(expression.to_cnf().is_ok());
+}
@@ -0,0 +1,306 @@
+use flp_gsp::{Bounds, Expression, Limit, Node, ParseErrorKind, ParseLimits, ParseOptions};
+
+#[test]
+fn test_error_position() {
+    let s = r#"("a" = "b" junk)"#;
+    let error = Expression::try_from_str(s).unwrap_err();
+
+    assert_eq!(error.offset, 11);
+    assert_eq!((error.line, error.column), (1, 12));
+    assert_eq!(error.kind, ParseErrorKind::Expected(vec!["&", "|", ")"]));
+    assert_eq!(error.snippet, "junk)");
+    assert_eq!(
+        error.to_string(),
+        "expected `&`, `|` or `)` at line 1, column 12, found `junk)`"
+    );
+}
+
+#[test]
+fn test_error_expected() {
+    let s = r#"(("a" = "b") & "c" # "d")"#;
+    let error = Expression::try_from_str(s).unwrap_err();
+    assert_eq!(error.offset, 19);
+    assert_eq!(
+        error.kind,
+        ParseErrorKind::Expected(vec![
+            "=", "~", ">", "<", ">=", "<=", "!=", "..", "*=", "^=", "$=", "*", "$", "$~", "?", "-"
+        ])
+    );
+
+    let s = r#"("a" ? ["x", ])"#;
+    let error = Expression::try_from_str(s).unwrap_err();
+    assert_eq!(error.offset, 13);
+    assert_eq!(error.kind, ParseErrorKind::Expected(vec!["string"]));
+
+    let s = r#"("a" = "b"#;
+    let error = Expression::try_from_str(s).unwrap_err();
+    assert_eq!(error.offset, 9);
+    assert_eq!(error.kind, ParseErrorKind::Expected(vec!["\""]));
+    assert_eq!(
+        error.to_string(),
+        "expected `\"` at line 1, column 10, found end of input"
+    );
+}
+
+#[test]
+fn test_trailing_input() {
+    let s = r#"("a" = "b") junk"#;
+    let error = Expression::try_from_str(s).unwrap_err();
+    assert_eq!(error.offset, 12);
+    assert_eq!(
+        error.kind,
+        ParseErrorKind::Expected(vec!["&", "|", "end of input"])
+    );
+    assert_eq!(
+        error.to_string(),
+        "expected `&`, `|` or end of input at line 1, column 13, found `junk`"
+    );
+
+    assert!(Expression::try_from_str("(\"a\" = \"b\")  \t").is_ok());
+
+    let options = ParseOptions {
+        lenient: true,
+        ..Default::default()
+    };
+    assert!(Expression::try_from_str_with(s, &options).is_ok());
+    for s in [r#"("a"="b") & junk"#, r#"("a"="b") | "#] {
+        assert_eq!(
+            Expression::try_from_str_with(s, &options).unwrap(),
+            Expression::try_from_str(r#""a" = "b""#).unwrap(),
+            "{}",
+            s
+        );
+    }
+}
+
+fn nested(depth: usize) -> String {
+    let mut s = r#"("k" = "v")"#.to_string();
+    for i in 0..depth {
+        s = match i % 3 {
+            0 => format!(r#"({} & "k" = "v")"#, s),
+            1 => format!(r#"("k" - | {})"#, s),
+            _ => format!("(! {})", s),
+        };
+    }
+    s
+}
+
+/// Nodes in the expression of `nested(depth)`.
+fn nested_nodes(depth: usize) -> usize {
+    1 + (0..depth)
+        .map(|i| if i % 3 == 2 { 1 } else { 2 })
+        .sum::<usize>()
+}
+
+#[test]
+fn test_nesting_is_linear() {
+    // Debug builds need far more stack per level than the default limits allow for.
+    let parse = std::thread::Builder::new()
+        .stack_size(64 * 1024 * 1024)
+        .spawn(move || {
+            for depth in [20, 100, 300] {
+                let s = nested(depth);
+                // Every node counts against the limit each time it is built, so a parse that
+                // fits exactly as many nodes as the expression has never parsed any twice.
+                let options = |max_nodes| ParseOptions {
+                    limits: ParseLimits {
+                        max_depth: 1024,
+                        max_nodes,
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                };
+                let nodes = nested_nodes(depth);
+                assert!(Expression::try_from_str_with(&s, &options(nodes)).is_ok());
+                assert_eq!(
+                    Expression::try_from_str_with(&s, &options(nodes - 1))
+                        .unwrap_err()
+                        .kind,
+                    ParseErrorKind::LimitExceeded {
+                        limit: Limit::Nodes,
+                        max: nodes - 1
+                    }
+                );
+            }
+        })
+        .unwrap();
+    parse.join().unwrap();
+}
+
+#[test]
+fn test_limits() {
+    let s = nested(200);
+    let error = Expression::try_from_str(&s).unwrap_err();
+    assert_eq!(
+        error.kind,
+        ParseErrorKind::LimitExceeded {
+            limit: Limit::Depth,
+            max: 64
+        }
+    );
+
+    let s = "(".repeat(60_000);
+    let error = Expression::try_from_str(&s).unwrap_err();
+    assert_eq!(error.offset, 64);
+
+    let limits = |limits: ParseLimits| ParseOptions {
+        limits,
+        ..Default::default()
+    };
+
+    let s = r#"("a" ? ["1", "2", "3"])"#;
+    let options = limits(ParseLimits {
+        max_array_length: 2,
+        ..Default::default()
+    });
+    let error = Expression::try_from_str_with(s, &options).unwrap_err();
+    assert_eq!(error.offset, 18);
+    assert_eq!(
+        error.to_string(),
+        "array length exceeds the limit of 2 at line 1, column 19, found `\"3\"])`"
+    );
+
+    let s = r#"(("a" = "1") & ("b" = "22"))"#;
+    let options = limits(ParseLimits {
+        max_text_length: 1,
+        ..Default::default()
+    });
+    let error = Expression::try_from_str_with(s, &options).unwrap_err();
+    assert_eq!(error.offset, 22);
+
+    let options = limits(ParseLimits {
+        max_nodes: 2,
+        ..Default::default()
+    });
+    let error = Expression::try_from_str_with(s, &options).unwrap_err();
+    assert_eq!(
+        error.kind,
+        ParseErrorKind::LimitExceeded {
+            limit: Limit::Nodes,
+            max: 2
+        }
+    );
+
+    let options = limits(ParseLimits {
+        max_input_length: 10,
+        ..Default::default()
+    });
+    let error = Expression::try_from_str_with(s, &options).unwrap_err();
+    assert_eq!(error.offset, 10);
+}
+
+#[test]
+fn test_precedence() {
+    let same = |a: &str, b: &str| {
+        assert_eq!(
+            Expression::try_from_str(a).unwrap(),
+            Expression::try_from_str(b).unwrap()
+        )
+    };
+    same(r#""a" = "1""#, r#"("a" = "1")"#);
+    same(
+        r#"("a" = "1" & "b" = "2" & "c" = "3")"#,
+        r#"(("a" = "1" & "b" = "2") & "c" = "3")"#,
+    );
+    same(
+        r#""a" = "1" | "b" = "2" & ! "c" = "3""#,
+        r#"("a" = "1" | ("b" = "2" & (! "c" = "3")))"#,
+    );
+    same(
+        r#"! ! "a" - & ("b" ? [] | "c" > "1")"#,
+        r#"((! (! "a" -)) & ("b" ? [] | "c" > "1"))"#,
+    );
+}
+
+#[test]
+fn test_flatten() {
+    let s = r#"("a" = "1" & ("b" = "2" & "c" = "3")) & ("d" - | "e" - | ("f" - | "g" -))"#;
+    let expression = Expression::try_from_str(s).unwrap();
+    match expression.node {
+        Node::All(expressions) => {
+            assert_eq!(expressions.len(), 4);
+            assert!(matches!(&expressions[3].node, Node::AnyOf(e) if e.len() == 4));
+        }
+        _ => panic!("expected a conjunction"),
+    }
+}
+
+#[test]
+fn test_comparison_operators() {
+    let s = r#""a" >= "1" & "b"<="2" & !"c" != "3""#;
+    let expression = Expression::try_from_str(s).unwrap();
+    assert_eq!(
+        expression.node,
+        Node::All(vec![
+            Node::GreaterEqual("a".into(), "1".into()).into(),
+            Node::LessEqual("b".into(), "2".into()).into(),
+            Node::Not(Box::new(Node::NotEqual("c".into(), "3".into()).into())).into(),
+        ])
+    );
+    assert_eq!(
+        expression.to_string(),
+        r#""a" >= "1" & "b" <= "2" & !"c" != "3""#
+    );
+
+    let error = Expression::try_from_str(r#""a" >= junk"#).unwrap_err();
+    assert_eq!(error.offset, 7);
+    assert_eq!(error.kind, ParseErrorKind::Expected(vec!["string"]));
+}
+
+#[test]
+fn test_between() {
+    let s = r#""price" .. ["10", "20") | "date" ..("2020-01-01","2021-01-01"]"#;
+    let expression = Expression::try_from_str(s).unwrap();
+    assert_eq!(
+        expression.node,
+        Node::AnyOf(vec![
+            Node::Between(
+                "price".into(),
+                Bounds {
+                    lower: "10".into(),
+                    lower_inclusive: true,
+                    upper: "20".into(),
+                    upper_inclusive: false,
+                }
+            )
+            .into(),
+            Node::Between(
+                "date".into(),
+                Bounds {
+                    lower: "2020-01-01".into(),
+                    lower_inclusive: false,
+                    upper: "2021-01-01".into(),
+                    upper_inclusive: true,
+                }
+            )
+            .into(),
+        ])
+    );
+    assert_eq!(
+        expression.to_string(),
+        r#""price" .. ["10", "20") | "date" .. ("2020-01-01", "2021-01-01"]"#
+    );
+
+    let error = Expression::try_from_str(r#""price" .. "10""#).unwrap_err();
+    assert_eq!(error.offset, 11);
+    assert_eq!(error.kind, ParseErrorKind::Expected(vec!["[", "("]));
+    let error = Expression::try_from_str(r#""price" .. ["10", "20"}"#).unwrap_err();
+    assert_eq!(error.kind, ParseErrorKind::Expected(vec!["]", ")"]));
+}
+
+#[test]
+fn test_string_operators() {
+    let s = r#""a" *= "x" | "b" ^= "y" | "c" $= "z" | "d" $ "^z" | "e" * "*z""#;
+    let expression = Expression::try_from_str(s).unwrap();
+    assert_eq!(
+        expression.node,
+        Node::AnyOf(vec![
+            Node::Contains("a".into(), "x".into()).into(),
+            Node::StartsWith("b".into(), "y".into()).into(),
+            Node::EndsWith("c".into(), "z".into()).into(),
+            Node::Regex("d".into(), "^z".into()).into(),
+            Node::Wildcard("e".into(), "*z".into()).into(),
+        ])
+    );
+    assert_eq!(expression.to_string(), s);
+}
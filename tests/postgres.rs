@@ -4,7 +4,7 @@ use flp_gsp::{interpreter::postgres::*, Expression};
 
 #[test]
 fn test_postgres() {
-    let s = r#"((((! "age" -) & (! "age" > "18")) & ("sex" ? ["male", "Male"] | "sex" ~ "Female")) & "\"name\"" * "J?c*")"#;
+    let s = r#"((((! "age" -) & (! "age" > "18")) & ("sex" ? ["male", "Male"] | "sex" ~ "Female")) & "name" * "J?c*")"#;
     let expression = Expression::try_from_str(s).unwrap();
     println!("{:?}", expression);
 
@@ -14,14 +14,14 @@ fn test_postgres() {
     let mut types = PostgresTypes::new();
     types.insert("age".into(), PostgresType::Int(None));
     types.insert("sex".into(), PostgresType::StringLike(None));
-    types.insert("\"name\"".into(), PostgresType::StringLike(None));
+    types.insert("name".into(), PostgresType::StringLike(None));
 
     let interpreted = interpret(&expression, &renames, &types, 1).unwrap();
     let (clause, binds) = interpreted;
 
     assert_eq!(
         clause,
-        "((((NOT age IS NULL) AND (NOT age > $1)) AND (gender IN ($2, $3) OR gender ILIKE $4)) AND \"name\" ILIKE $5)"
+        r#"((NOT "age" IS NULL) AND (NOT "age" > $1) AND ("gender" IN ($2, $3) OR "gender" ILIKE $4 ESCAPE '\') AND "name" LIKE $5 ESCAPE '\')"#
     );
     assert_eq!(
         binds,
@@ -34,3 +34,86 @@ fn test_postgres() {
         ]
     );
 }
+
+#[test]
+fn test_postgres_comparisons() {
+    let s = r#""age" >= "18" & "age" <= "65" & "sex" != "Other" & "age" .. ["20", "30"] & "age" .. ("20", "30"]"#;
+    let expression = Expression::try_from_str(s).unwrap();
+
+    let mut types = PostgresTypes::new();
+    types.insert("age".into(), PostgresType::Int(None));
+    types.insert("sex".into(), PostgresType::StringLike(None));
+
+    let (clause, binds) = interpret(&expression, &PostgresRenames::new(), &types, 1).unwrap();
+    assert_eq!(
+        clause,
+        r#"("age" >= $1 AND "age" <= $2 AND "sex" <> $3 AND "age" BETWEEN $4 AND $5 AND ("age" > $6 AND "age" <= $7))"#
+    );
+    assert_eq!(
+        binds,
+        vec![
+            PostgresType::Int(Some(18)),
+            PostgresType::Int(Some(65)),
+            PostgresType::StringLike(Some("Other".into())),
+            PostgresType::Int(Some(20)),
+            PostgresType::Int(Some(30)),
+            PostgresType::Int(Some(20)),
+            PostgresType::Int(Some(30))
+        ]
+    );
+}
+
+#[test]
+fn test_postgres_regex() {
+    let s = r#""name" $ "^J.*" | "name" $~ "k$""#;
+    let expression = Expression::try_from_str(s).unwrap();
+
+    let mut types = PostgresTypes::new();
+    types.insert("name".into(), PostgresType::StringLike(None));
+
+    let (clause, binds) = interpret(&expression, &PostgresRenames::new(), &types, 1).unwrap();
+    assert_eq!(clause, r#"("name" ~ $1 OR "name" ~* $2)"#);
+    assert_eq!(
+        binds,
+        vec![
+            PostgresType::StringLike(Some("^J.*".into())),
+            PostgresType::StringLike(Some("k$".into()))
+        ]
+    );
+}
+
+#[test]
+fn test_postgres_placeholders() {
+    let s = r#""a??b" = "1" & "c" ? ["2", "3"]"#;
+    let expression = Expression::try_from_str(s).unwrap();
+
+    let mut types = PostgresTypes::new();
+    types.insert("a??b".into(), PostgresType::Int(None));
+    types.insert("c".into(), PostgresType::Int(None));
+
+    let (clause, binds) = interpret(&expression, &PostgresRenames::new(), &types, 4).unwrap();
+    assert_eq!(clause, r#"("a??b" = $4 AND "c" IN ($5, $6))"#);
+    assert_eq!(
+        binds,
+        vec![
+            PostgresType::Int(Some(1)),
+            PostgresType::Int(Some(2)),
+            PostgresType::Int(Some(3))
+        ]
+    );
+}
+
+#[test]
+fn test_postgres_unnumbered() {
+    let s = r#""age" > "18" | "sex" ? ["male", "Male"]"#;
+    let expression = Expression::try_from_str(s).unwrap();
+
+    let mut types = PostgresTypes::new();
+    types.insert("age".into(), PostgresType::Int(None));
+    types.insert("sex".into(), PostgresType::StringLike(None));
+
+    let (clause, binds) =
+        interpret_expression(&expression, &PostgresRenames::new(), &types).unwrap();
+    assert_eq!(clause, r#"("age" > ?? OR "sex" IN (??, ??))"#);
+    assert_eq!(binds.len(), 3);
+}
@@ -0,0 +1,73 @@
+#![cfg(feature = "serde")]
+
+use flp_gsp::{Expression, Node};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+#[test]
+fn test_json() {
+    let s = r#"!"age" - & ("sex" ? ["male", "Male"] | "sex" ~ "Female") & "name" * "J?c*" & "age" .. ["18", "65")"#;
+    let expression = Expression::try_from_str(s).unwrap();
+
+    let value = serde_json::to_value(&expression).unwrap();
+    assert_eq!(
+        value,
+        json!({
+            "op": "all",
+            "args": [
+                {"op": "not", "arg": {"op": "null", "key": "age"}},
+                {"op": "any_of", "args": [
+                    {"op": "any", "key": "sex", "values": ["male", "Male"]},
+                    {"op": "equal_ci", "key": "sex", "value": "Female"},
+                ]},
+                {"op": "wildcard", "key": "name", "value": "J?c*"},
+                {"op": "between", "key": "age", "lower": "18", "lower_inclusive": true, "upper": "65", "upper_inclusive": false},
+            ],
+        })
+    );
+
+    let deserialized: Expression = serde_json::from_value(value).unwrap();
+    assert_eq!(deserialized, expression);
+}
+
+#[test]
+fn test_gss_field() {
+    #[derive(Serialize, Deserialize)]
+    struct SavedSearch {
+        name: String,
+        #[serde(with = "flp_gsp::serialization::gss")]
+        filter: Expression,
+    }
+
+    let json = r#"{"name":"adults","filter":"\"age\" > \"18\" & !\"age\" -"}"#;
+    let saved: SavedSearch = serde_json::from_str(json).unwrap();
+    assert_eq!(saved.filter.to_string(), r#""age" > "18" & !"age" -"#);
+    assert_eq!(serde_json::to_string(&saved).unwrap(), json);
+
+    let json = r#"{"name":"broken","filter":"\"age\" >"}"#;
+    assert!(serde_json::from_str::<SavedSearch>(json).is_err());
+    for filter in [Node::All(vec![]), Node::AnyOf(vec![])] {
+        let saved = SavedSearch {
+            name: "constant".to_string(),
+            filter: Node::Not(Box::new(filter.into())).into(),
+        };
+        assert!(serde_json::to_string(&saved).is_err());
+    }
+
+    // Shapes the parser does not produce are normalized.
+    let equal = |key: &str| Expression::from(Node::Equal(key.into(), "1".into()));
+    let saved = SavedSearch {
+        name: "nested".to_string(),
+        filter: Node::All(vec![
+            Node::AnyOf(vec![equal("a")]).into(),
+            Node::All(vec![equal("b"), equal("c")]).into(),
+        ])
+        .into(),
+    };
+    let json = serde_json::to_string(&saved).unwrap();
+    let restored: SavedSearch = serde_json::from_str(&json).unwrap();
+    assert_eq!(
+        restored.filter,
+        Node::All(vec![equal("a"), equal("b"), equal("c")]).into()
+    );
+}
@@ -0,0 +1,39 @@
+use flp_gsp::{Expression, Node};
+
+fn simplify(s: &str) -> String {
+    Expression::try_from_str(s).unwrap().simplify().to_string()
+}
+
+#[test]
+fn test_simplify() {
+    assert_eq!(simplify(r#"(! (! "a" = "b"))"#), r#""a" = "b""#);
+    assert_eq!(
+        simplify(r#"!("a" = "1" & !"b" -)"#),
+        r#"!"a" = "1" | "b" -"#
+    );
+    assert_eq!(
+        simplify(r#""c" - & !("a" = "1" | ("b" - | "d" > "2"))"#),
+        r#""c" - & !"a" = "1" & !"b" - & !"d" > "2""#
+    );
+    assert_eq!(
+        simplify(r#""a" - & ("b" - & "a" -) & "b" -"#),
+        r#""a" - & "b" -"#
+    );
+    assert_eq!(
+        simplify(r#""a" = "1" | "b" < "3" | "a" = "2" | "a" ? ["1", "3"]"#),
+        r#""a" ? ["1", "2", "3"] | "b" < "3""#
+    );
+    assert_eq!(simplify(r#""a" = "1" | "a" = "1""#), r#""a" = "1""#);
+}
+
+#[test]
+fn test_simplify_constants() {
+    let constant = |s: &str| Expression::try_from_str(s).unwrap().simplify().node;
+    assert_eq!(constant(r#""x" ? []"#), Node::AnyOf(vec![]));
+    assert_eq!(constant(r#"!"x" ? []"#), Node::All(vec![]));
+    assert_eq!(constant(r#""a" = "1" & "x" ? []"#), Node::AnyOf(vec![]));
+    assert_eq!(constant(r#""a" = "1" | !"x" ? []"#), Node::All(vec![]));
+
+    assert_eq!(simplify(r#""a" = "1" | "x" ? []"#), r#""a" = "1""#);
+    assert_eq!(simplify(r#""a" = "1" & !"x" ? []"#), r#""a" = "1""#);
+}
@@ -21,7 +21,7 @@ fn test_sqlite() {
 
     assert_eq!(
         clause,
-        "((((NOT age IS NULL) AND (NOT age > ?)) AND (gender IN (?, ?) OR gender LIKE ?)) AND t.name LIKE ?)"
+        r#"((NOT "age" IS NULL) AND (NOT "age" > ?) AND ("gender" IN (?, ?) OR "gender" LIKE ? ESCAPE '\') AND "t"."name" GLOB ?)"#
     );
     assert_eq!(
         binds,
@@ -30,7 +30,209 @@ fn test_sqlite() {
             SqliteType::Text(Some("male".into())),
             SqliteType::Text(Some("Male".into())),
             SqliteType::Text(Some("Female".into())),
-            SqliteType::Text(Some("J_c%".into()))
+            SqliteType::Text(Some("J?c*".into()))
         ]
     );
 }
+
+#[test]
+fn test_sqlite_string_operators() {
+    let s = r#""name" *= "50%_off" | "name" ^= "J*" | "name" $= "[k?]""#;
+    let expression = Expression::try_from_str(s).unwrap();
+
+    let mut types = SqliteTypes::new();
+    types.insert("name".into(), SqliteType::Text(None));
+
+    let (clause, binds) = interpret(&expression, &SqliteRenames::new(), &types).unwrap();
+    assert_eq!(
+        clause,
+        r#"("name" GLOB ? OR "name" GLOB ? OR "name" GLOB ?)"#
+    );
+    assert_eq!(
+        binds,
+        vec![
+            SqliteType::Text(Some("*50%_off*".into())),
+            SqliteType::Text(Some("J[*]*".into())),
+            SqliteType::Text(Some("*[[]k[?]]".into()))
+        ]
+    );
+}
+
+#[test]
+fn test_sqlite_escaping() {
+    let s = r#""name" * "100\\%*" | "name" ~ "a%_" | "name" * "\\*?%\\""#;
+    let expression = Expression::try_from_str(s).unwrap();
+
+    let mut types = SqliteTypes::new();
+    types.insert("name".into(), SqliteType::Text(None));
+
+    let (clause, binds) = interpret(&expression, &SqliteRenames::new(), &types).unwrap();
+    assert_eq!(
+        clause,
+        r#"("name" GLOB ? OR "name" LIKE ? ESCAPE '\' OR "name" GLOB ?)"#
+    );
+    assert_eq!(
+        binds,
+        vec![
+            SqliteType::Text(Some("100%*".into())),
+            SqliteType::Text(Some(r"a\%\_".into())),
+            SqliteType::Text(Some(r"[*]?%\".into()))
+        ]
+    );
+}
+
+#[test]
+fn test_sqlite_regex() {
+    let s = r#""name" $ "^J.*" | "name" $~ "k$""#;
+    let expression = Expression::try_from_str(s).unwrap();
+
+    let mut types = SqliteTypes::new();
+    types.insert("name".into(), SqliteType::Text(None));
+
+    let (clause, binds) = interpret(&expression, &SqliteRenames::new(), &types).unwrap();
+    assert_eq!(clause, r#"("name" REGEXP ? OR "name" REGEXP ?)"#);
+    assert_eq!(
+        binds,
+        vec![
+            SqliteType::Text(Some("^J.*".into())),
+            SqliteType::Text(Some("(?i)k$".into()))
+        ]
+    );
+}
+
+/// Asserts that each search selects the same names from a table of `names` in SQLite as
+/// the evaluator passes, and at least one.
+#[cfg(all(feature = "rusqlite", feature = "evaluate"))]
+fn assert_matches_evaluate(names: &[&str], searches: &[&str]) {
+    use flp_gsp::interpreter::evaluate::{self, EvaluatePairs, EvaluateRule, EvaluateRules};
+
+    let connection = rusqlite::Connection::open_in_memory().unwrap();
+    register_regexp(&connection).unwrap();
+    connection
+        .execute("CREATE TABLE people (name TEXT NOT NULL)", [])
+        .unwrap();
+    for name in names {
+        connection
+            .execute("INSERT INTO people (name) VALUES (?)", [name])
+            .unwrap();
+    }
+
+    let mut types = SqliteTypes::new();
+    types.insert("name".into(), SqliteType::Text(None));
+    let mut rules = EvaluateRules::new();
+    rules.insert("name".into(), EvaluateRule::default());
+
+    for s in searches {
+        let expression = Expression::try_from_str(s).unwrap();
+
+        let (clause, binds) = interpret(&expression, &SqliteRenames::new(), &types).unwrap();
+        let mut statement = connection
+            .prepare(&format!(
+                "SELECT name FROM people WHERE {} ORDER BY rowid",
+                clause
+            ))
+            .unwrap();
+        let selected: Vec<String> = statement
+            .query_map(rusqlite::params_from_iter(binds), |row| row.get(0))
+            .unwrap()
+            .collect::<rusqlite::Result<_>>()
+            .unwrap();
+
+        let evaluated: Vec<String> = names
+            .iter()
+            .filter(|name| {
+                let mut pairs = EvaluatePairs::new();
+                pairs.insert("name".into(), name.to_string());
+                evaluate::interpret(&expression, &rules, &pairs)
+            })
+            .map(|name| name.to_string())
+            .collect();
+
+        assert!(!selected.is_empty(), "{}", s);
+        assert_eq!(selected, evaluated, "{}", s);
+    }
+}
+
+#[cfg(all(feature = "rusqlite", feature = "evaluate"))]
+#[test]
+fn test_sqlite_regexp_matches_evaluate() {
+    assert_matches_evaluate(
+        &["Jack", "jack", "Jill", "Mike", "Kojak"],
+        &[
+            r#""name" $ "^J""#,
+            r#""name" $~ "^j""#,
+            r#""name" $ "ck$" & !"name" $~ "^k""#,
+            r#""name" $~ "^(jack|mike)$" | "name" $ "i""#,
+        ],
+    );
+}
+
+#[cfg(all(feature = "rusqlite", feature = "evaluate"))]
+#[test]
+fn test_sqlite_string_operators_match_evaluate() {
+    assert_matches_evaluate(
+        &["Jack", "jack", "JACK", "50%_off", "50% off", "a*[b]?"],
+        &[
+            r#""name" *= "ac""#,
+            r#""name" ^= "J""#,
+            r#""name" $= "CK""#,
+            r#""name" *= "%_""#,
+            r#""name" ^= "a*[""#,
+            r#""name" $= "]?""#,
+        ],
+    );
+}
+
+#[cfg(all(feature = "rusqlite", feature = "evaluate"))]
+#[test]
+fn test_sqlite_wildcard_matches_evaluate() {
+    assert_matches_evaluate(
+        &["Jack", "jack", "JACK", "Jill", "50%_off", "a*[b]?"],
+        &[
+            r#""name" * "J*""#,
+            r#""name" * "?ack""#,
+            r#""name" * "*CK""#,
+            r#""name" * "*%_*""#,
+            r#""name" * "a\\*[*\\?""#,
+            r#""name" * "j*" | "name" ~ "jill""#,
+        ],
+    );
+}
+
+#[test]
+fn test_sqlite_identifiers() {
+    let types = {
+        let mut types = SqliteTypes::new();
+        types.insert("name".into(), SqliteType::Text(None));
+        types
+    };
+    let interpret_str = |s: &str| {
+        interpret(
+            &Expression::try_from_str(s).unwrap(),
+            &SqliteRenames::new(),
+            &types,
+        )
+    };
+
+    for s in [
+        r#""1=1 OR x" = "1""#,
+        r#""1=1 OR x" -"#,
+        r#""1=1 OR x" ? []"#,
+    ] {
+        assert!(
+            matches!(interpret_str(s), Err(Error::UnknownKey(_))),
+            "{}",
+            s
+        );
+    }
+
+    let mut types = types.clone();
+    types.insert(r#"x" OR 1=1 --"#.into(), SqliteType::Text(None));
+    let (clause, _) = interpret(
+        &Expression::try_from_str(r#""x\" OR 1=1 --" -"#).unwrap(),
+        &SqliteRenames::new(),
+        &types,
+    )
+    .unwrap();
+    assert_eq!(clause, r#""x"" OR 1=1 --" IS NULL"#);
+}
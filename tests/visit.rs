@@ -0,0 +1,61 @@
+use std::collections::BTreeSet;
+
+use flp_gsp::{
+    visit::{Fold, Visitor},
+    Expression, Node,
+};
+
+#[derive(Default)]
+struct Keys(BTreeSet<String>);
+
+impl Visitor for Keys {
+    fn visit_equal(&mut self, key: &str, _target: &str) {
+        self.0.insert(key.to_string());
+    }
+
+    fn visit_any(&mut self, key: &str, _targets: &[String]) {
+        self.0.insert(key.to_string());
+    }
+
+    fn visit_null(&mut self, key: &str) {
+        self.0.insert(key.to_string());
+    }
+}
+
+#[test]
+fn test_visitor() {
+    let s = r#""a" = "1" & !("b" - | "c" ? ["2"]) & "d" > "3""#;
+    let expression = Expression::try_from_str(s).unwrap();
+
+    let mut keys = Keys::default();
+    keys.visit_expression(&expression);
+    assert_eq!(keys.0.into_iter().collect::<Vec<_>>(), vec!["a", "b", "c"]);
+}
+
+/// Makes every equality case-insensitive and expands `Null` into an explicit check for an
+/// empty string as well.
+struct Loosen;
+
+impl Fold for Loosen {
+    fn fold_equal(&mut self, key: String, target: String) -> Expression {
+        Node::EqualCI(key, target).into()
+    }
+
+    fn fold_null(&mut self, key: String) -> Expression {
+        Node::AnyOf(vec![
+            Node::Null(key.clone()).into(),
+            Node::Equal(key, String::new()).into(),
+        ])
+        .into()
+    }
+}
+
+#[test]
+fn test_fold() {
+    let s = r#""a" = "1" & !("b" - | "c" ? ["2"]) & "d" > "3""#;
+    let expression = Loosen.fold_expression(Expression::try_from_str(s).unwrap());
+    assert_eq!(
+        expression.to_string(),
+        r#""a" ~ "1" & !(("b" - | "b" = "") | "c" ? ["2"]) & "d" > "3""#
+    );
+}